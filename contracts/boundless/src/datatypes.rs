@@ -1,24 +1,56 @@
-use soroban_sdk::{contracterror, contracttype, Address, String, Vec};
+use soroban_sdk::{contracterror, contracttype, Address, BytesN, String, Vec};
 
 // Constants
 pub(crate) const DAY_IN_LEDGERS: u32 = 17280; // Assuming 5 seconds per ledger
 pub(crate) const PROJECTS_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
 pub(crate) const PROJECTS_LIFETIME_THRESHOLD: u32 = PROJECTS_BUMP_AMOUNT - DAY_IN_LEDGERS;
 
+// Denominator for percentages expressed in basis points
+pub(crate) const BPS_DENOMINATOR: u32 = 10_000;
+
 // Fixed periods in days
 pub(crate) const FUNDING_PERIOD_DAYS: u32 = 30;
 pub(crate) const VOTING_PERIOD_DAYS: u32 = 30;
+pub(crate) const MILESTONE_GRACE_PERIOD_DAYS: u32 = 7;
+pub(crate) const OVERDUE_VOTING_PERIOD_DAYS: u32 = 7;
+pub(crate) const MILESTONE_EXTENSION_DAYS: u32 = 30;
+pub(crate) const AMENDMENT_VOTING_PERIOD_DAYS: u32 = 7;
+pub(crate) const CHALLENGE_PERIOD_DAYS: u32 = 3;
 
 // Convert days to ledgers
 pub(crate) const FUNDING_PERIOD_LEDGERS: u32 = FUNDING_PERIOD_DAYS * DAY_IN_LEDGERS;
 pub(crate) const VOTING_PERIOD_LEDGERS: u32 = VOTING_PERIOD_DAYS * DAY_IN_LEDGERS;
 
+// Convert days to seconds, for periods compared against the ledger timestamp
+pub(crate) const DAY_IN_SECONDS: u64 = 86_400;
+pub(crate) const MILESTONE_GRACE_PERIOD_SECONDS: u64 =
+    MILESTONE_GRACE_PERIOD_DAYS as u64 * DAY_IN_SECONDS;
+pub(crate) const OVERDUE_VOTING_PERIOD_SECONDS: u64 =
+    OVERDUE_VOTING_PERIOD_DAYS as u64 * DAY_IN_SECONDS;
+pub(crate) const MILESTONE_EXTENSION_SECONDS: u64 =
+    MILESTONE_EXTENSION_DAYS as u64 * DAY_IN_SECONDS;
+pub(crate) const AMENDMENT_VOTING_PERIOD_SECONDS: u64 =
+    AMENDMENT_VOTING_PERIOD_DAYS as u64 * DAY_IN_SECONDS;
+pub(crate) const CHALLENGE_PERIOD_SECONDS: u64 = CHALLENGE_PERIOD_DAYS as u64 * DAY_IN_SECONDS;
+
+// Default share of a project's funding needed to challenge a milestone approval
+pub(crate) const CHALLENGE_THRESHOLD_BPS: u32 = 1_000;
+// Default cap on the share of funding a creator can take upfront
+pub(crate) const MAX_UPFRONT_BPS: u32 = 2_000;
+// Maximum number of recipients a project's payouts can be split between
+pub(crate) const MAX_PAYOUT_RECIPIENTS: u32 = 10;
+// Maximum number of members in a project team
+pub(crate) const MAX_TEAM_MEMBERS: u32 = 10;
+// Maximum number of entries returned by a paginated getter
+pub(crate) const MAX_PAGE_SIZE: u32 = 50;
+
 /// Enum representing all possible data keys for contract storage
 #[contracttype]
 pub enum DataKey {
     Version,
     Initialized,
     Admin,
+    Config,
     Projects,
     Project(String),
     Backers(String),
@@ -26,6 +58,40 @@ pub enum DataKey {
     Milestones(String),
     WhitelistedTokens(String),
     RefundedTokens(String),
+    OverdueVote(String),
+    MilestoneAmendment(String),
+    MilestonePayout(String, u32),
+    VestingStreams(String),
+    UpfrontTranche(String),
+    BondRequirement,
+    CreatorBond(String),
+    PayoutSplits(String),
+    PayoutSplitProposal(String),
+    ProjectTeam(String),
+    TeamApprovals(String),
+    PendingOwner(String),
+    MetadataHistory(String),
+    MetadataProposal(String),
+    ProjectUpdateCount(String),
+    ProjectUpdate(String, u32),
+    RewardTiers(String),
+    BackerTiers(String, Address),
+    StretchGoals(String),
+    DonationsClaimed(String, Address),
+    DeferredPledges(String),
+    Pledges(String),
+    PledgesSettled(String),
+    RecurringPledge(String, Address),
+    RecurringRevenue(String),
+    Attestors,
+    Attestation(String, String),
+    NonStandardTokens,
+    Escrow(String, Address),
+    EscrowTotal(Address),
+    ReturnsClaimed(String, Address, Address),
+    Released(String, Address),
+    ReturnedFunds(String),
+    Refunded(String, Address),
 }
 
 #[contracterror]
@@ -80,6 +146,28 @@ pub enum BoundlessError {
     TransferFailed = 23,
     /// Balance check failed
     BalanceCheckFailed = 24,
+    /// Milestone is not past its due date and grace period
+    MilestoneNotOverdue = 25,
+    /// Milestone plan can no longer be edited directly
+    MilestonePlanLocked = 26,
+    /// Percentage or basis-point share is out of range
+    InvalidShare = 27,
+    /// Challenge window for the milestone approval is closed
+    ChallengePeriodEnded = 28,
+    /// Challenge window for the milestone approval is still open
+    ChallengePeriodActive = 29,
+    /// Funds have already been claimed
+    AlreadyClaimed = 30,
+    /// Reward tier does not exist
+    TierNotFound = 31,
+    /// Reward tier has no quantity left
+    TierSoldOut = 32,
+    /// Pledge is below the reward tier's minimum
+    PledgeBelowTierMinimum = 33,
+    /// Project escrow ledgers would exceed the contract's token balance
+    EscrowExceedsBalance = 34,
+    /// Amount does not fit in the contract's `u64` accounting
+    AmountTooLarge = 35,
 }
 
 /// Enum representing the current status of a project
@@ -96,6 +184,10 @@ pub enum ProjectStatus {
     Failed = 4,
     /// Project has been closed by creator
     Closed = 5,
+    /// Project was cancelled by backers, unreleased escrow is refundable
+    Cancelled = 6,
+    /// Every milestone has been approved and paid out
+    Completed = 7,
 }
 
 /// Enum representing the status of a milestone
@@ -110,6 +202,122 @@ pub enum MilestoneStatus {
     Approved,
     /// Milestone has been rejected
     Rejected,
+    /// Milestone missed its due date and is awaiting a backer decision
+    Overdue,
+}
+
+/// Enum representing a sensitive project action that needs team approval
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub enum TeamAction {
+    /// Replace the project's metadata URI and content hash
+    UpdateMetadata(String, BytesN<32>),
+    /// Change the project's milestone count
+    SetMilestoneCount(u32),
+    /// Close the project
+    CloseProject,
+    /// Add a member to the team
+    AddMember(Address),
+    /// Remove a member from the team
+    RemoveMember(Address),
+    /// Change the number of approvals actions need
+    SetThreshold(u32),
+    /// Hand the project to a new creator address
+    TransferOwnership(Address),
+    /// Reserve an upfront tranche of the funding, in basis points
+    SetUpfrontBps(u32),
+    /// Replace the payout recipients, or propose it to backers once funded
+    SetPayoutSplits(Vec<PayoutSplit>),
+    /// Cancel a funded project and slash the creator bond
+    AbandonProject,
+    /// Append a milestone with a description, amount and due date
+    AddMilestone(String, u64, u64),
+    /// Propose replacing the pending tail of the milestone plan
+    AmendMilestones(Vec<MilestonePlanItem>),
+    /// Add a stretch goal with a threshold, description and milestones
+    AddStretchGoal(u64, String, Vec<MilestonePlanItem>),
+}
+
+/// Enum representing what happens to the unapproved part of a partially approved milestone
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RemainderAction {
+    /// Remainder becomes a new follow-up milestone at the end of the plan
+    RollOver = 1,
+    /// Remainder is returned to backers pro rata
+    Refund = 2,
+}
+
+/// Enum representing how a project handles funds relative to its target
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FundingModel {
+    /// Funds are released through milestones only if the target is reached
+    AllOrNothing = 1,
+    /// Funds are released through milestones even below the target
+    KeepItAll = 2,
+    /// No milestones, funds are claimable immediately minus fees
+    Donation = 3,
+}
+
+/// Enum representing an accounting check performed by an audit
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AuditCheck {
+    /// Stored backer contributions plus attested funds against `total_funded`
+    BackerRecords = 1,
+    /// `Project.backers` plus attested funds against `total_funded`
+    BackerList = 2,
+    /// Released and returned funds against `total_funded`
+    Outflows = 3,
+    /// Amounts paid on milestones against `total_released`
+    MilestonePayouts = 4,
+    /// Amounts returned on milestones against `total_returned`
+    MilestoneReturns = 5,
+    /// A token's escrow ledger against what was funded in that token
+    EscrowLedger = 6,
+    /// Escrow ledgers of all projects in a token against the contract's balance
+    TokenBalance = 7,
+}
+
+/// Enum representing where a slashed creator bond goes
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SlashTarget {
+    /// Bond is split between backers pro rata to their contributions
+    Backers = 1,
+    /// Bond is sent to the platform treasury
+    Treasury = 2,
+}
+
+/// Enum representing the state of a creator bond
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BondStatus {
+    /// Bond is held by the contract
+    Held = 1,
+    /// Bond has been returned to the creator
+    Returned = 2,
+    /// Bond has been slashed
+    Slashed = 3,
+}
+
+/// Enum representing the reason given for a milestone review decision
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ReviewReason {
+    /// Deliverables match the milestone description
+    Delivered = 1,
+    /// Deliverables are missing or only partially complete
+    IncompleteDeliverables = 2,
+    /// Deliverables do not meet the expected quality
+    QualityIssues = 3,
+    /// Submitted evidence could not be verified against its hash
+    UnverifiableEvidence = 4,
+    /// Deliverables do not match the milestone description
+    ScopeMismatch = 5,
+    /// Any other reason, detailed in the feedback
+    Other = 6,
 }
 
 // Event Structures
@@ -156,6 +364,11 @@ pub struct MilestoneReleasedEvent {
 pub struct MilestoneApprovedEvent {
     pub project_id: String,
     pub milestone_number: u32,
+    pub approved_amount: u64,
+    pub reviewer: Address,
+    pub reason: ReviewReason,
+    pub feedback_uri: String,
+    pub feedback_hash: BytesN<32>,
 }
 
 #[contracttype]
@@ -163,6 +376,10 @@ pub struct MilestoneApprovedEvent {
 pub struct MilestoneRejectedEvent {
     pub project_id: String,
     pub milestone_number: u32,
+    pub reviewer: Address,
+    pub reason: ReviewReason,
+    pub feedback_uri: String,
+    pub feedback_hash: BytesN<32>,
 }
 
 #[contracttype]
@@ -177,7 +394,7 @@ pub struct RefundProcessedEvent {
 #[derive(Clone)]
 #[contracttype]
 pub struct Milestone {
-    /// Milestone number (1-based index)
+    /// Milestone number (0-based index into `Project.milestones`)
     pub number: u32,
     /// Description of the milestone
     pub description: String,
@@ -189,6 +406,386 @@ pub struct Milestone {
     pub released_at: Option<u64>,
     /// Timestamp when the milestone was approved/rejected
     pub completed_at: Option<u64>,
+    /// Timestamp by which the milestone must be delivered
+    pub due_date: u64,
+    /// Amount approved and paid out to the creator
+    pub paid_amount: u64,
+    /// Unapproved amount returned to backers
+    pub returned_amount: u64,
+    /// Amount of the payout held back until the project completes
+    pub held_amount: u64,
+    /// Follow-up milestone that the unapproved amount was rolled into
+    pub rolled_over_to: Option<u32>,
+    /// Deliverables submitted by the creator for review, latest submission last
+    pub evidence: Vec<MilestoneEvidence>,
+    /// Review decisions on this milestone, latest decision last
+    pub reviews: Vec<MilestoneReview>,
+}
+
+/// Struct representing the evidence a creator submits for a milestone
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct MilestoneEvidence {
+    /// External URI of the deliverables
+    pub uri: String,
+    /// Hash of the deliverables, so reviewers can verify what was submitted
+    pub hash: BytesN<32>,
+    /// Timestamp of submission
+    pub submitted_at: u64,
+}
+
+/// Struct representing a reviewer's decision on a submitted milestone
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct MilestoneReview {
+    /// Address of the reviewer
+    pub reviewer: Address,
+    /// Whether the milestone was approved
+    pub approved: bool,
+    /// Reason code for the decision
+    pub reason: ReviewReason,
+    /// External URI of the reviewer's feedback
+    pub feedback_uri: String,
+    /// Hash of the reviewer's feedback
+    pub feedback_hash: BytesN<32>,
+    /// Timestamp of the decision
+    pub reviewed_at: u64,
+}
+
+/// Struct representing a milestone remainder returned to backers
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ReturnedFunds {
+    /// Milestone the remainder came from
+    pub milestone_number: u32,
+    /// Amount returned
+    pub amount: u64,
+    /// Project funding when the remainder was returned
+    pub total_funded: u64,
+    /// Number of recorded contributions that share in the return
+    pub contributions: u32,
+}
+
+/// Struct representing an approved milestone payout waiting out its challenge window
+#[derive(Clone)]
+#[contracttype]
+pub struct MilestonePayout {
+    /// Milestone number that was approved
+    pub milestone_number: u32,
+    /// Amount the creator can claim once the window closes
+    pub approved_amount: u64,
+    /// Unapproved amount of the milestone
+    pub remainder_amount: u64,
+    /// What happens to the unapproved amount when the payout is claimed
+    pub remainder: RemainderAction,
+    /// Timestamp from which the creator can claim the payout
+    pub claimable_at: u64,
+    /// Contribution weight of the backers challenging the approval
+    pub challenge_weight: u64,
+    /// Backers who have challenged the approval
+    pub challengers: Vec<Address>,
+}
+
+/// Struct representing a milestone payout that vests linearly to the creator
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct VestingStream {
+    /// Milestone number the payout belongs to
+    pub milestone_number: u32,
+    /// Total amount vesting over the stream
+    pub amount: u64,
+    /// Amount already withdrawn by the creator
+    pub withdrawn: u64,
+    /// Timestamp when vesting started
+    pub start: u64,
+    /// Time over which the amount vests
+    pub duration: u64,
+}
+
+/// Struct representing the share of funding released to the creator once a
+/// project is funded, ahead of any milestone
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct UpfrontTranche {
+    /// Share of the total funding, in basis points
+    pub bps: u32,
+    /// Amount paid to the creator, zero until claimed
+    pub claimed_amount: u64,
+    /// Timestamp when the tranche was claimed
+    pub claimed_at: Option<u64>,
+}
+
+/// Struct representing the backer vote on an overdue milestone
+#[derive(Clone)]
+#[contracttype]
+pub struct OverdueVote {
+    /// Milestone number that was marked overdue
+    pub milestone_number: u32,
+    /// Timestamp when the milestone was marked overdue
+    pub marked_at: u64,
+    /// Timestamp when voting on the overdue milestone ends
+    pub voting_deadline: u64,
+    /// Contribution weight in favour of extending the due date
+    pub extend_weight: u64,
+    /// Contribution weight in favour of cancelling the project
+    pub cancel_weight: u64,
+    /// Backers who have voted
+    pub voters: Vec<Address>,
+}
+
+/// Struct representing a milestone in a proposed plan amendment
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct MilestonePlanItem {
+    /// Description of the milestone
+    pub description: String,
+    /// Amount to be released for this milestone
+    pub amount: u64,
+    /// Timestamp by which the milestone must be delivered
+    pub due_date: u64,
+}
+
+/// Struct representing extra scope unlocked once funding passes a threshold
+#[derive(Clone)]
+#[contracttype]
+pub struct StretchGoal {
+    /// Total funding at which the goal unlocks
+    pub threshold: u64,
+    /// Description of the goal
+    pub description: String,
+    /// Milestones appended to the plan when the goal unlocks
+    pub milestones: Vec<MilestonePlanItem>,
+    /// Timestamp when funding reached the threshold, `None` while locked
+    pub reached_at: Option<u64>,
+}
+
+/// Struct representing a creator's proposal to change the milestone plan
+#[derive(Clone)]
+#[contracttype]
+pub struct MilestoneAmendment {
+    /// First milestone number replaced by the proposal
+    pub first_milestone: u32,
+    /// Number of milestones in the plan when the amendment was proposed
+    pub plan_length: u32,
+    /// Milestones replacing every milestone from `first_milestone` onwards
+    pub milestones: Vec<MilestonePlanItem>,
+    /// Timestamp when the amendment was proposed
+    pub proposed_at: u64,
+    /// Timestamp when voting on the amendment ends
+    pub voting_deadline: u64,
+    /// Contribution weight in favour of the amendment
+    pub approve_weight: u64,
+    /// Contribution weight against the amendment
+    pub reject_weight: u64,
+    /// Backers who have voted
+    pub voters: Vec<Address>,
+}
+
+/// Struct representing one recipient's share of a project's payouts
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct PayoutSplit {
+    /// Address receiving the share
+    pub recipient: Address,
+    /// Share of every payout in basis points
+    pub bps: u32,
+}
+
+/// Struct representing a creator's proposal to change the payout split
+#[derive(Clone)]
+#[contracttype]
+pub struct PayoutSplitProposal {
+    /// Split replacing the current one
+    pub splits: Vec<PayoutSplit>,
+    /// Timestamp when the change was proposed
+    pub proposed_at: u64,
+    /// Timestamp when voting on the change ends
+    pub voting_deadline: u64,
+    /// Contribution weight in favour of the change
+    pub approve_weight: u64,
+    /// Contribution weight against the change
+    pub reject_weight: u64,
+    /// Backers who have voted
+    pub voters: Vec<Address>,
+}
+
+/// Struct representing one version of a project's metadata
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct MetadataVersion {
+    /// Version number, starting at 1
+    pub version: u32,
+    /// Metadata external URI
+    pub uri: String,
+    /// Hash of the metadata content
+    pub hash: BytesN<32>,
+    /// Timestamp when the version took effect
+    pub updated_at: u64,
+}
+
+/// Struct representing a creator's proposal to change the project metadata
+#[derive(Clone)]
+#[contracttype]
+pub struct MetadataProposal {
+    /// Proposed metadata external URI
+    pub uri: String,
+    /// Hash of the proposed metadata content
+    pub hash: BytesN<32>,
+    /// Timestamp when the change was proposed
+    pub proposed_at: u64,
+    /// Timestamp when voting on the change ends
+    pub voting_deadline: u64,
+    /// Contribution weight in favour of the change
+    pub approve_weight: u64,
+    /// Contribution weight against the change
+    pub reject_weight: u64,
+    /// Backers who have voted
+    pub voters: Vec<Address>,
+}
+
+/// Struct representing a progress update posted by a project's creator
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ProjectUpdate {
+    /// Position of the update in the project's log, starting at 0
+    pub index: u32,
+    /// Update content external URI
+    pub uri: String,
+    /// Hash of the update content
+    pub hash: BytesN<32>,
+    /// Milestone the update relates to, if any
+    pub milestone_number: Option<u32>,
+    /// Timestamp when the update was posted
+    pub posted_at: u64,
+}
+
+/// Struct representing a reward tier's minimum pledge in a specific token
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct TierPrice {
+    /// Token contract address
+    pub token: Address,
+    /// Minimum pledge in that token
+    pub min_amount: u64,
+}
+
+/// Struct representing a perk backers receive for pledging at least a minimum
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RewardTier {
+    /// Tier id, its position in the project's tiers
+    pub id: u32,
+    /// Tier title
+    pub title: String,
+    /// Tier metadata external URI
+    pub metadata_uri: String,
+    /// Minimum pledge in tokens without a specific price
+    pub min_pledge: u64,
+    /// Token-specific minimum pledges
+    pub prices: Vec<TierPrice>,
+    /// Number of pledges the tier accepts, unlimited if `None`
+    pub quantity: Option<u32>,
+    /// Number of pledges that claimed the tier
+    pub claimed: u32,
+}
+
+/// Struct representing the members who jointly own a project
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ProjectTeam {
+    /// Team member addresses
+    pub members: Vec<Address>,
+    /// Number of member approvals sensitive actions need
+    pub threshold: u32,
+}
+
+/// Struct representing the approvals collected so far for a team action
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct TeamApproval {
+    /// Action being approved
+    pub action: TeamAction,
+    /// Members who have approved the action
+    pub approvals: Vec<Address>,
+}
+
+/// Struct representing a pledge charged through the backer's allowance once
+/// the funding period ends
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct Pledge {
+    /// Backer's address
+    pub backer: Address,
+    /// Amount pledged
+    pub amount: u64,
+    /// Token contract address
+    pub token: Address,
+    /// Timestamp of the pledge
+    pub pledged_at: u64,
+    /// Reward tier claimed with the pledge, if any
+    pub tier_id: Option<u32>,
+}
+
+/// Struct representing a single failed accounting check
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct AuditDiscrepancy {
+    /// Check that failed
+    pub check: AuditCheck,
+    /// Token the check applies to, if it is per token
+    pub token: Option<Address>,
+    /// Value the check expected
+    pub expected: i128,
+    /// Value recorded on-chain
+    pub actual: i128,
+}
+
+/// Struct representing the result of auditing a project's accounting
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct AuditReport {
+    /// Audited project
+    pub project_id: String,
+    /// Total funded as recorded on the project
+    pub total_funded: u64,
+    /// Failed checks, empty when the accounting is consistent
+    pub discrepancies: Vec<AuditDiscrepancy>,
+}
+
+/// Struct representing a contribution paid off-chain and attested on-chain
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct AttestedContribution {
+    /// Reference of the off-chain payment, unique per project
+    pub reference: String,
+    /// Backer credited with the contribution
+    pub backer: Address,
+    /// Amount contributed
+    pub amount: u64,
+    /// Attestor who recorded the contribution
+    pub attestor: Address,
+    /// Timestamp of the attestation
+    pub attested_at: u64,
+}
+
+/// Struct representing a backer's standing authorisation to be charged every period
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RecurringPledge {
+    /// Backer's address
+    pub backer: Address,
+    /// Token contract address
+    pub token: Address,
+    /// Amount charged every period
+    pub amount: u64,
+    /// Time between charges
+    pub period: u64,
+    /// Timestamp from which the next charge can be made
+    pub next_charge_at: u64,
+    /// Total amount charged so far
+    pub total_charged: u64,
+    /// False once the backer has cancelled
+    pub active: bool,
 }
 
 /// Struct representing a backer's contribution to a project
@@ -260,6 +857,66 @@ pub struct Project {
     pub status: ProjectStatus,
     /// Milestones for this project
     pub milestones: Vec<Milestone>,
+    /// Total amount released from escrow to the creator
+    pub total_released: u64,
+    /// Total amount returned to backers from partially approved milestones
+    pub total_returned: u64,
+    /// Amount held back from milestone payouts and still in escrow
+    pub total_held_back: u64,
+    /// How funds are handled relative to the funding target
+    pub funding_model: FundingModel,
+    /// Portion of `total_funded` attested as paid off-chain
+    pub total_attested: u64,
+    /// Attested off-chain contributions per backer, never refunded on-chain
+    pub attested_backers: Vec<(Address, u64)>,
+}
+
+/// Struct representing platform-wide settings managed by the admin
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct PlatformConfig {
+    /// Time between a milestone approval and its payout becoming claimable
+    pub challenge_period: u64,
+    /// Share of a project's funding, in basis points, needed to challenge an approval
+    pub challenge_threshold_bps: u32,
+    /// Time over which claimed milestone payouts vest, zero pays them out at once
+    pub vesting_duration: u64,
+    /// Cap on the share of funding, in basis points, a project can release upfront
+    pub max_upfront_bps: u32,
+    /// Share of every milestone payout, in basis points, held back until the project completes
+    pub holdback_bps: u32,
+    /// Platform fee, in basis points, taken from donations when they are claimed
+    pub donation_fee_bps: u32,
+}
+
+/// Struct representing the bond creators must deposit when creating a project
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct BondRequirement {
+    /// Token the bond is deposited in
+    pub token: Address,
+    /// Bond amount, zero disables the requirement
+    pub amount: i128,
+    /// Where slashed bonds go
+    pub slash_target: SlashTarget,
+    /// Platform treasury receiving slashed bonds
+    pub treasury: Address,
+}
+
+/// Struct representing the bond a creator deposited for a project
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct CreatorBond {
+    /// Token the bond was deposited in
+    pub token: Address,
+    /// Bond amount
+    pub amount: i128,
+    /// Current state of the bond
+    pub status: BondStatus,
+    /// Where the bond goes if it is slashed
+    pub slash_target: SlashTarget,
+    /// Platform treasury receiving the bond if it is slashed
+    pub treasury: Address,
 }
 
 #[contracttype]
@@ -1,12 +1,30 @@
 use soroban_sdk::{Address, BytesN, Env, String, Vec};
 
-use crate::datatypes::{BoundlessError, Milestone, MilestoneStatus, Project, ProjectStatus};
+use crate::datatypes::{
+    AttestedContribution, AuditReport, BondRequirement, BoundlessError, CreatorBond, FundingModel,
+    MetadataProposal, MetadataVersion, Milestone, MilestoneAmendment, MilestonePayout,
+    MilestonePlanItem, MilestoneReview, MilestoneStatus, OverdueVote, PayoutSplit,
+    PayoutSplitProposal, PlatformConfig, Pledge, Project, ProjectStatus, ProjectTeam,
+    ProjectUpdate, RecurringPledge, RemainderAction, ReviewReason, RewardTier, StretchGoal,
+    TeamApproval, TierPrice, UpfrontTranche, VestingStream,
+};
 
 pub trait ContractManagement {
     fn initialize(env: Env, admin: Address) -> Result<(), BoundlessError>;
     fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), BoundlessError>;
     fn get_admin(e: &Env) -> Address;
     fn get_version(e: &Env) -> u32;
+    fn set_config(env: Env, admin: Address, config: PlatformConfig) -> Result<(), BoundlessError>;
+    fn get_config(env: Env) -> PlatformConfig;
+    fn set_bond_requirement(
+        env: Env,
+        admin: Address,
+        requirement: BondRequirement,
+    ) -> Result<(), BoundlessError>;
+    fn get_bond_requirement(env: Env) -> Result<BondRequirement, BoundlessError>;
+    fn add_attestor(env: Env, admin: Address, attestor: Address) -> Result<(), BoundlessError>;
+    fn remove_attestor(env: Env, admin: Address, attestor: Address) -> Result<(), BoundlessError>;
+    fn get_attestors(env: Env) -> Vec<Address>;
 }
 
 pub trait ProjectManagement {
@@ -15,8 +33,10 @@ pub trait ProjectManagement {
         project_id: String,
         creator: Address,
         metadata_uri: String,
+        metadata_hash: BytesN<32>,
         funding_target: u64,
         milestone_count: u32,
+        funding_model: FundingModel,
     ) -> Result<(), BoundlessError>;
     fn get_project(env: Env, project_id: String) -> Result<Project, BoundlessError>;
     fn update_project_metadata(
@@ -24,7 +44,38 @@ pub trait ProjectManagement {
         project_id: String,
         creator: Address,
         new_metadata_uri: String,
+        metadata_hash: BytesN<32>,
     ) -> Result<(), BoundlessError>;
+    fn vote_metadata_update(
+        env: Env,
+        project_id: String,
+        backer: Address,
+        approve: bool,
+    ) -> Result<(), BoundlessError>;
+    fn finalize_metadata_update(env: Env, project_id: String) -> Result<bool, BoundlessError>;
+    fn get_metadata_proposal(
+        env: Env,
+        project_id: String,
+    ) -> Result<MetadataProposal, BoundlessError>;
+    fn get_metadata_history(
+        env: Env,
+        project_id: String,
+    ) -> Result<Vec<MetadataVersion>, BoundlessError>;
+    fn post_project_update(
+        env: Env,
+        project_id: String,
+        creator: Address,
+        uri: String,
+        hash: BytesN<32>,
+        milestone_number: Option<u32>,
+    ) -> Result<u32, BoundlessError>;
+    fn get_project_updates(
+        env: Env,
+        project_id: String,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<ProjectUpdate>, BoundlessError>;
+    fn get_project_update_count(env: Env, project_id: String) -> Result<u32, BoundlessError>;
     fn update_project_milestone_count(
         env: Env,
         project_id: String,
@@ -37,12 +88,86 @@ pub trait ProjectManagement {
         caller: Address,
         new_milestone_count: u32,
     ) -> Result<(), BoundlessError>;
+    fn set_upfront_bps(
+        env: Env,
+        project_id: String,
+        creator: Address,
+        upfront_bps: u32,
+    ) -> Result<(), BoundlessError>;
+    fn set_payout_splits(
+        env: Env,
+        project_id: String,
+        creator: Address,
+        splits: Vec<PayoutSplit>,
+    ) -> Result<(), BoundlessError>;
+    fn vote_payout_splits(
+        env: Env,
+        project_id: String,
+        backer: Address,
+        approve: bool,
+    ) -> Result<(), BoundlessError>;
+    fn finalize_payout_splits(env: Env, project_id: String) -> Result<bool, BoundlessError>;
+    fn get_payout_splits(env: Env, project_id: String) -> Result<Vec<PayoutSplit>, BoundlessError>;
+    fn get_payout_split_proposal(
+        env: Env,
+        project_id: String,
+    ) -> Result<PayoutSplitProposal, BoundlessError>;
+    fn transfer_project_ownership(
+        env: Env,
+        project_id: String,
+        creator: Address,
+        new_owner: Address,
+    ) -> Result<(), BoundlessError>;
+    fn accept_project_ownership(
+        env: Env,
+        project_id: String,
+        new_owner: Address,
+    ) -> Result<(), BoundlessError>;
+    fn get_pending_owner(env: Env, project_id: String) -> Result<Address, BoundlessError>;
     fn close_project(env: Env, project_id: String, creator: Address) -> Result<(), BoundlessError>;
+    fn abandon_project(
+        env: Env,
+        project_id: String,
+        creator: Address,
+    ) -> Result<(), BoundlessError>;
+    fn force_fail_project(
+        env: Env,
+        admin: Address,
+        project_id: String,
+    ) -> Result<(), BoundlessError>;
+    fn get_creator_bond(env: Env, project_id: String) -> Result<CreatorBond, BoundlessError>;
+    fn reclaim_bond(env: Env, project_id: String) -> Result<(), BoundlessError>;
     fn get_project_status(env: Env, project_id: String) -> Result<ProjectStatus, BoundlessError>;
     fn list_projects(env: Env) -> Result<Vec<String>, BoundlessError>;
     fn get_project_stats(env: Env, project_id: String) -> Result<(u64, u64, u32), BoundlessError>;
 }
 
+pub trait TeamOperations {
+    fn add_team_member(
+        env: Env,
+        project_id: String,
+        caller: Address,
+        member: Address,
+    ) -> Result<(), BoundlessError>;
+    fn remove_team_member(
+        env: Env,
+        project_id: String,
+        caller: Address,
+        member: Address,
+    ) -> Result<(), BoundlessError>;
+    fn set_team_threshold(
+        env: Env,
+        project_id: String,
+        caller: Address,
+        threshold: u32,
+    ) -> Result<(), BoundlessError>;
+    fn get_project_team(env: Env, project_id: String) -> Result<ProjectTeam, BoundlessError>;
+    fn get_pending_team_actions(
+        env: Env,
+        project_id: String,
+    ) -> Result<Vec<TeamApproval>, BoundlessError>;
+}
+
 pub trait VotingOperations {
     fn vote_project(
         env: Env,
@@ -56,6 +181,22 @@ pub trait VotingOperations {
 }
 
 pub trait MilestoneOperations {
+    fn add_milestone(
+        env: Env,
+        project_id: String,
+        creator: Address,
+        description: String,
+        amount: u64,
+        due_date: u64,
+    ) -> Result<u32, BoundlessError>;
+    fn submit_milestone(
+        env: Env,
+        project_id: String,
+        milestone_number: u32,
+        creator: Address,
+        evidence_uri: String,
+        evidence_hash: BytesN<32>,
+    ) -> Result<(), BoundlessError>;
     fn release_milestone(
         env: Env,
         project_id: String,
@@ -67,13 +208,56 @@ pub trait MilestoneOperations {
         project_id: String,
         milestone_number: u32,
         admin: Address,
+        reason: ReviewReason,
+        feedback_uri: String,
+        feedback_hash: BytesN<32>,
     ) -> Result<(), BoundlessError>;
+    fn approve_milestone_partial(
+        env: Env,
+        project_id: String,
+        milestone_number: u32,
+        admin: Address,
+        approved_bps: u32,
+        remainder: RemainderAction,
+        reason: ReviewReason,
+        feedback_uri: String,
+        feedback_hash: BytesN<32>,
+    ) -> Result<(), BoundlessError>;
+    fn challenge_milestone(
+        env: Env,
+        project_id: String,
+        milestone_number: u32,
+        backer: Address,
+    ) -> Result<(), BoundlessError>;
+    fn claim_milestone_payout(
+        env: Env,
+        project_id: String,
+        milestone_number: u32,
+    ) -> Result<(), BoundlessError>;
+    fn get_milestone_payout(
+        env: Env,
+        project_id: String,
+        milestone_number: u32,
+    ) -> Result<MilestonePayout, BoundlessError>;
+    fn withdraw_vested(env: Env, project_id: String) -> Result<u64, BoundlessError>;
+    fn get_vesting_streams(
+        env: Env,
+        project_id: String,
+    ) -> Result<Vec<VestingStream>, BoundlessError>;
     fn reject_milestone(
         env: Env,
         project_id: String,
         milestone_number: u32,
         admin: Address,
+        reason: ReviewReason,
+        feedback_uri: String,
+        feedback_hash: BytesN<32>,
     ) -> Result<(), BoundlessError>;
+    fn get_milestone_reviews(
+        env: Env,
+        project_id: String,
+        milestone_number: u32,
+    ) -> Result<Vec<MilestoneReview>, BoundlessError>;
     fn get_milestone_status(
         env: Env,
         project_id: String,
@@ -83,6 +267,46 @@ pub trait MilestoneOperations {
         env: Env,
         project_id: String,
     ) -> Result<Vec<Milestone>, BoundlessError>;
+    fn get_overdue_milestones(env: Env, project_id: String) -> Result<Vec<u32>, BoundlessError>;
+    fn mark_overdue(
+        env: Env,
+        project_id: String,
+        milestone_number: u32,
+    ) -> Result<(), BoundlessError>;
+    fn vote_overdue(
+        env: Env,
+        project_id: String,
+        backer: Address,
+        extend: bool,
+    ) -> Result<(), BoundlessError>;
+    fn resolve_overdue(env: Env, project_id: String) -> Result<(), BoundlessError>;
+    fn get_overdue_vote(env: Env, project_id: String) -> Result<OverdueVote, BoundlessError>;
+    fn propose_milestone_amendment(
+        env: Env,
+        project_id: String,
+        creator: Address,
+        milestones: Vec<MilestonePlanItem>,
+    ) -> Result<(), BoundlessError>;
+    fn vote_milestone_amendment(
+        env: Env,
+        project_id: String,
+        backer: Address,
+        approve: bool,
+    ) -> Result<(), BoundlessError>;
+    fn finalize_milestone_amendment(env: Env, project_id: String) -> Result<bool, BoundlessError>;
+    fn get_milestone_amendment(
+        env: Env,
+        project_id: String,
+    ) -> Result<MilestoneAmendment, BoundlessError>;
+    fn add_stretch_goal(
+        env: Env,
+        project_id: String,
+        creator: Address,
+        threshold: u64,
+        description: String,
+        milestones: Vec<MilestonePlanItem>,
+    ) -> Result<u32, BoundlessError>;
+    fn get_stretch_goals(env: Env, project_id: String) -> Result<Vec<StretchGoal>, BoundlessError>;
 }
 
 pub trait FundingOperations {
@@ -92,6 +316,16 @@ pub trait FundingOperations {
         amount: i128,
         funder: Address,
         token_contract: Address,
+        tier_id: Option<u32>,
+    ) -> Result<(), BoundlessError>;
+    fn fund_project_for(
+        env: Env,
+        project_id: String,
+        payer: Address,
+        beneficiary: Address,
+        amount: i128,
+        token_contract: Address,
+        tier_id: Option<u32>,
     ) -> Result<(), BoundlessError>;
     fn refund(env: Env, project_id: String, token_contract: Address) -> Result<(), BoundlessError>;
     fn get_project_funding(env: Env, project_id: String) -> Result<(u64, u64), BoundlessError>;
@@ -100,10 +334,116 @@ pub trait FundingOperations {
         project_id: String,
         backer: Address,
     ) -> Result<u64, BoundlessError>;
+    fn claim_upfront(env: Env, project_id: String) -> Result<u64, BoundlessError>;
+    fn get_upfront_tranche(env: Env, project_id: String) -> Result<UpfrontTranche, BoundlessError>;
+    fn finalize_funding(env: Env, project_id: String) -> Result<ProjectStatus, BoundlessError>;
+    fn set_deferred_pledges(
+        env: Env,
+        project_id: String,
+        creator: Address,
+        enabled: bool,
+    ) -> Result<(), BoundlessError>;
+    fn settle_pledges(
+        env: Env,
+        project_id: String,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<Pledge>, BoundlessError>;
+    fn get_pledges(env: Env, project_id: String) -> Result<Vec<Pledge>, BoundlessError>;
+    fn attest_contribution(
+        env: Env,
+        attestor: Address,
+        project_id: String,
+        reference: String,
+        backer: Address,
+        amount: u64,
+    ) -> Result<bool, BoundlessError>;
+    fn get_attestation(
+        env: Env,
+        project_id: String,
+        reference: String,
+    ) -> Result<AttestedContribution, BoundlessError>;
+    fn get_funding_breakdown(env: Env, project_id: String) -> Result<(u64, u64), BoundlessError>;
+    fn create_recurring_pledge(
+        env: Env,
+        project_id: String,
+        backer: Address,
+        token_contract: Address,
+        amount: u64,
+        period: u64,
+    ) -> Result<(), BoundlessError>;
+    fn charge_recurring_pledge(
+        env: Env,
+        project_id: String,
+        backer: Address,
+    ) -> Result<u64, BoundlessError>;
+    fn cancel_recurring_pledge(
+        env: Env,
+        project_id: String,
+        backer: Address,
+    ) -> Result<(), BoundlessError>;
+    fn get_recurring_pledge(
+        env: Env,
+        project_id: String,
+        backer: Address,
+    ) -> Result<RecurringPledge, BoundlessError>;
+    fn get_recurring_revenue(env: Env, project_id: String) -> Result<u64, BoundlessError>;
+    fn claim_returned_funds(
+        env: Env,
+        project_id: String,
+        backer: Address,
+        token_contract: Address,
+    ) -> Result<u64, BoundlessError>;
+    fn get_claimable_returns(
+        env: Env,
+        project_id: String,
+        backer: Address,
+        token_contract: Address,
+    ) -> Result<u64, BoundlessError>;
+    fn claim_donations(
+        env: Env,
+        project_id: String,
+        token_contract: Address,
+    ) -> Result<u64, BoundlessError>;
+    fn add_reward_tier(
+        env: Env,
+        project_id: String,
+        creator: Address,
+        title: String,
+        metadata_uri: String,
+        min_pledge: u64,
+        prices: Vec<TierPrice>,
+        quantity: Option<u32>,
+    ) -> Result<u32, BoundlessError>;
+    fn get_reward_tiers(env: Env, project_id: String) -> Result<Vec<RewardTier>, BoundlessError>;
+    fn get_tier_remaining(
+        env: Env,
+        project_id: String,
+        tier_id: u32,
+    ) -> Result<Option<u32>, BoundlessError>;
+    fn get_backer_tiers(
+        env: Env,
+        project_id: String,
+        backer: Address,
+    ) -> Result<Vec<u32>, BoundlessError>;
     fn whitelist_token_contract(
         env: Env,
         admin: Address,
         project_id: String,
         token_contract: Address,
     ) -> Result<(), BoundlessError>;
+    fn get_escrow_balance(env: Env, project_id: String, token_contract: Address) -> u64;
+    fn set_token_non_standard(
+        env: Env,
+        admin: Address,
+        token_contract: Address,
+        non_standard: bool,
+    ) -> Result<(), BoundlessError>;
+    fn is_token_non_standard(env: Env, token_contract: Address) -> bool;
+}
+
+pub trait AuditOperations {
+    fn audit_project(env: Env, project_id: String) -> Result<AuditReport, BoundlessError>;
+    fn audit_projects(env: Env, start: u32, limit: u32)
+        -> Result<Vec<AuditReport>, BoundlessError>;
 }
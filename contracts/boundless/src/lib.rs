@@ -1,4 +1,6 @@
 #![no_std]
+// Contract entrypoints take their arguments individually
+#![allow(clippy::too_many_arguments)]
 
 use soroban_sdk::contract;
 
@@ -1,9 +1,12 @@
 use crate::{
-    datatypes::{BoundlessError, DataKey},
+    datatypes::{
+        BondRequirement, BoundlessError, DataKey, PlatformConfig, BPS_DENOMINATOR,
+        CHALLENGE_PERIOD_SECONDS, CHALLENGE_THRESHOLD_BPS, MAX_UPFRONT_BPS,
+    },
     interface::ContractManagement,
     BoundlessContract, BoundlessContractArgs, BoundlessContractClient,
 };
-use soroban_sdk::{contractimpl, Address, BytesN, Env};
+use soroban_sdk::{contractimpl, Address, BytesN, Env, Vec};
 
 #[contractimpl]
 impl ContractManagement for BoundlessContract {
@@ -46,4 +49,96 @@ impl ContractManagement for BoundlessContract {
     fn get_version(e: &Env) -> u32 {
         e.storage().persistent().get(&DataKey::Version).unwrap_or(0)
     }
+    fn set_config(env: Env, admin: Address, config: PlatformConfig) -> Result<(), BoundlessError> {
+        admin.require_auth();
+        if Self::get_admin(&env) != admin {
+            return Err(BoundlessError::Unauthorized);
+        }
+        if config.challenge_threshold_bps == 0 || config.challenge_threshold_bps > BPS_DENOMINATOR {
+            return Err(BoundlessError::InvalidShare);
+        }
+        if config.donation_fee_bps > BPS_DENOMINATOR {
+            return Err(BoundlessError::InvalidShare);
+        }
+        if config.max_upfront_bps > BPS_DENOMINATOR || config.holdback_bps > BPS_DENOMINATOR {
+            return Err(BoundlessError::InvalidShare);
+        }
+        env.storage().persistent().set(&DataKey::Config, &config);
+        Ok(())
+    }
+    fn get_config(env: Env) -> PlatformConfig {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Config)
+            .unwrap_or(PlatformConfig {
+                challenge_period: CHALLENGE_PERIOD_SECONDS,
+                challenge_threshold_bps: CHALLENGE_THRESHOLD_BPS,
+                vesting_duration: 0,
+                max_upfront_bps: MAX_UPFRONT_BPS,
+                holdback_bps: 0,
+                donation_fee_bps: 0,
+            })
+    }
+    fn set_bond_requirement(
+        env: Env,
+        admin: Address,
+        requirement: BondRequirement,
+    ) -> Result<(), BoundlessError> {
+        admin.require_auth();
+        if Self::get_admin(&env) != admin {
+            return Err(BoundlessError::Unauthorized);
+        }
+        if requirement.amount < 0 {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::BondRequirement, &requirement);
+        Ok(())
+    }
+    fn get_bond_requirement(env: Env) -> Result<BondRequirement, BoundlessError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BondRequirement)
+            .ok_or(BoundlessError::NotFound)
+    }
+
+    fn add_attestor(env: Env, admin: Address, attestor: Address) -> Result<(), BoundlessError> {
+        admin.require_auth();
+        if Self::get_admin(&env) != admin {
+            return Err(BoundlessError::Unauthorized);
+        }
+        let mut attestors = Self::get_attestors(env.clone());
+        if attestors.contains(&attestor) {
+            return Err(BoundlessError::AlreadyExists);
+        }
+        attestors.push_back(attestor);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Attestors, &attestors);
+        Ok(())
+    }
+
+    fn remove_attestor(env: Env, admin: Address, attestor: Address) -> Result<(), BoundlessError> {
+        admin.require_auth();
+        if Self::get_admin(&env) != admin {
+            return Err(BoundlessError::Unauthorized);
+        }
+        let mut attestors = Self::get_attestors(env.clone());
+        let index = attestors
+            .first_index_of(&attestor)
+            .ok_or(BoundlessError::NotFound)?;
+        attestors.remove(index);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Attestors, &attestors);
+        Ok(())
+    }
+
+    fn get_attestors(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Attestors)
+            .unwrap_or(Vec::new(&env))
+    }
 }
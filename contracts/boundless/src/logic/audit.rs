@@ -0,0 +1,238 @@
+use crate::{
+    datatypes::{
+        AuditCheck, AuditDiscrepancy, AuditReport, BackerContribution, BoundlessError, DataKey,
+        Project, ProjectStatus, UpfrontTranche, MAX_PAGE_SIZE,
+    },
+    interface::{AuditOperations, FundingOperations},
+    logic::funding::funded_by_token,
+    BoundlessContract, BoundlessContractArgs, BoundlessContractClient,
+};
+use soroban_sdk::{contractimpl, token::TokenClient, Address, Env, String, Vec};
+
+#[contractimpl]
+impl AuditOperations for BoundlessContract {
+    fn audit_project(env: Env, project_id: String) -> Result<AuditReport, BoundlessError> {
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        let mut discrepancies = Vec::new(&env);
+
+        // Every unit of `total_funded` is backed by a token contribution or an attestation
+        let backer_records: Vec<BackerContribution> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Backers(project_id.clone()))
+            .unwrap_or(Vec::new(&env));
+        let mut recorded = project.total_attested as i128;
+        let mut i = 0;
+        while i < backer_records.len() {
+            recorded += backer_records.get_unchecked(i).amount as i128;
+            i += 1;
+        }
+        record_mismatch(
+            &mut discrepancies,
+            AuditCheck::BackerRecords,
+            None,
+            project.total_funded as i128,
+            recorded,
+        );
+
+        let funded = funded_by_token(&env, &project);
+        let mut listed = project.total_attested as i128;
+        for (_, amount) in funded.iter() {
+            listed += amount as i128;
+        }
+        record_mismatch(
+            &mut discrepancies,
+            AuditCheck::BackerList,
+            None,
+            project.total_funded as i128,
+            listed,
+        );
+
+        // An upper bound only: a project that is still delivering has released part of its funding
+        let outflows = project.total_released as i128 + project.total_returned as i128;
+        if outflows > project.total_funded as i128 {
+            record_mismatch(
+                &mut discrepancies,
+                AuditCheck::Outflows,
+                None,
+                project.total_funded as i128,
+                outflows,
+            );
+        }
+
+        // Releases are milestone payouts plus the upfront tranche, claimed
+        // donations and, once the project completes, the held back retention
+        let mut released = 0_i128;
+        let mut returned = 0_i128;
+        let mut i = 0;
+        while i < project.milestones.len() {
+            let milestone = project.milestones.get_unchecked(i);
+            released += milestone.paid_amount as i128;
+            if project.status == ProjectStatus::Completed {
+                released += milestone.held_amount as i128;
+            }
+            returned += milestone.returned_amount as i128;
+            i += 1;
+        }
+        let tranche: Option<UpfrontTranche> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UpfrontTranche(project_id.clone()));
+        if let Some(tranche) = tranche {
+            released += tranche.claimed_amount as i128;
+        }
+        for token in funded.keys().iter() {
+            let claimed: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::DonationsClaimed(project_id.clone(), token))
+                .unwrap_or(0);
+            released += claimed as i128;
+        }
+        record_mismatch(
+            &mut discrepancies,
+            AuditCheck::MilestonePayouts,
+            None,
+            project.total_released as i128,
+            released,
+        );
+        record_mismatch(
+            &mut discrepancies,
+            AuditCheck::MilestoneReturns,
+            None,
+            project.total_returned as i128,
+            returned,
+        );
+
+        let mut tokens: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WhitelistedTokens(project_id.clone()))
+            .unwrap_or(Vec::new(&env));
+        for token in funded.keys().iter() {
+            if !tokens.contains(&token) {
+                tokens.push_back(token);
+            }
+        }
+        let contract_address = env.current_contract_address();
+        let mut i = 0;
+        while i < tokens.len() {
+            let token = tokens.get_unchecked(i);
+            // The ledger holds what came in minus what was released, claimed back and refunded
+            let escrowed = Self::get_escrow_balance(env.clone(), project_id.clone(), token.clone());
+            let released: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Released(project_id.clone(), token.clone()))
+                .unwrap_or(0);
+            let refunded: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Refunded(project_id.clone(), token.clone()))
+                .unwrap_or(0);
+            let mut expected = funded.get(token.clone()).unwrap_or(0) as i128
+                - released as i128
+                - refunded as i128;
+            let mut j = 0;
+            while j < project.backers.len() {
+                let (backer, _, backer_token) = project.backers.get_unchecked(j);
+                if backer_token == token {
+                    let claimed: u64 = env
+                        .storage()
+                        .persistent()
+                        .get(&DataKey::ReturnsClaimed(
+                            project_id.clone(),
+                            backer,
+                            token.clone(),
+                        ))
+                        .unwrap_or(0);
+                    expected -= claimed as i128;
+                }
+                j += 1;
+            }
+            record_mismatch(
+                &mut discrepancies,
+                AuditCheck::EscrowLedger,
+                Some(token.clone()),
+                expected,
+                escrowed as i128,
+            );
+
+            // Only a shortfall is reported: bonds and direct transfers can
+            // legitimately raise the balance. An unreadable balance is
+            // reported as empty
+            let escrow_total: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::EscrowTotal(token.clone()))
+                .unwrap_or(0);
+            let balance = match TokenClient::new(&env, &token).try_balance(&contract_address) {
+                Ok(Ok(balance)) => balance,
+                _ => 0,
+            };
+            if escrow_total as i128 > balance {
+                record_mismatch(
+                    &mut discrepancies,
+                    AuditCheck::TokenBalance,
+                    Some(token),
+                    balance,
+                    escrow_total as i128,
+                );
+            }
+            i += 1;
+        }
+
+        Ok(AuditReport {
+            project_id,
+            total_funded: project.total_funded,
+            discrepancies,
+        })
+    }
+
+    fn audit_projects(
+        env: Env,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<AuditReport>, BoundlessError> {
+        let projects: Vec<String> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Projects)
+            .unwrap_or(Vec::new(&env));
+        let end = start
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(projects.len());
+        let mut reports = Vec::new(&env);
+        let mut index = start;
+        while index < end {
+            reports.push_back(Self::audit_project(
+                env.clone(),
+                projects.get_unchecked(index),
+            )?);
+            index += 1;
+        }
+        Ok(reports)
+    }
+}
+
+/// Records a discrepancy when a recomputed value differs from the expected one.
+fn record_mismatch(
+    discrepancies: &mut Vec<AuditDiscrepancy>,
+    check: AuditCheck,
+    token: Option<Address>,
+    expected: i128,
+    actual: i128,
+) {
+    if expected != actual {
+        discrepancies.push_back(AuditDiscrepancy {
+            check,
+            token,
+            expected,
+            actual,
+        });
+    }
+}
@@ -1,12 +1,18 @@
 use crate::{
     datatypes::{
-        BackerContribution, BoundlessError, DataKey, Project, ProjectFundedEvent, ProjectStatus,
-        RefundProcessedEvent,
+        AttestedContribution, BackerContribution, BoundlessError, DataKey, FundingModel,
+        PayoutSplit, Pledge, Project, ProjectFundedEvent, ProjectStatus, RecurringPledge,
+        RefundProcessedEvent, ReturnedFunds, RewardTier, TierPrice, UpfrontTranche,
+        BPS_DENOMINATOR, MAX_PAGE_SIZE,
+    },
+    interface::{ContractManagement, FundingOperations},
+    logic::{
+        milestone::{has_locked_stretch_goal, unlock_stretch_goals},
+        project::{is_milestone_plan_locked, payout_splits},
     },
-    interface::FundingOperations,
     BoundlessContract, BoundlessContractArgs, BoundlessContractClient,
 };
-use soroban_sdk::{contractimpl, token::TokenClient, Address, Env, String, Vec};
+use soroban_sdk::{contractimpl, token::TokenClient, Address, Env, Map, String, Vec};
 
 #[contractimpl]
 impl FundingOperations for BoundlessContract {
@@ -16,111 +22,49 @@ impl FundingOperations for BoundlessContract {
         amount: i128,
         funder: Address,
         token_contract: Address,
+        tier_id: Option<u32>,
     ) -> Result<(), BoundlessError> {
         funder.require_auth();
+        fund(
+            &env,
+            &project_id,
+            amount,
+            &funder,
+            &funder,
+            &token_contract,
+            tier_id,
+        )
+    }
 
-        if amount <= 0 {
-            return Err(BoundlessError::InsufficientFunds);
-        }
-
-        let mut project: Project = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Project(project_id.clone()))
-            .ok_or(BoundlessError::NotFound)?;
-
-        if project.status != ProjectStatus::Funding {
+    fn fund_project_for(
+        env: Env,
+        project_id: String,
+        payer: Address,
+        beneficiary: Address,
+        amount: i128,
+        token_contract: Address,
+        tier_id: Option<u32>,
+    ) -> Result<(), BoundlessError> {
+        payer.require_auth();
+        // Deferred pledges are pulled from the backer's own allowance
+        if payer != beneficiary && is_deferred(&env, &project_id) {
             return Err(BoundlessError::InvalidOperation);
         }
-
-        if project.is_closed {
-            return Err(BoundlessError::ProjectClosed);
-        }
-
-        let current_time = env.ledger().timestamp();
-
-        if current_time > project.funding_deadline {
-            return Err(BoundlessError::FundingPeriodEnded);
-        }
-
-        // Validate that the token contract is whitelisted/valid
-        let whitelisted_tokens: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::WhitelistedTokens(project_id.clone()))
-            .unwrap_or(Vec::new(&env));
-        if !whitelisted_tokens.contains(&token_contract) {
-            return Err(BoundlessError::InvalidTokenContract);
-        }
-
-        let token_client = TokenClient::new(&env, &token_contract);
-        token_client
-            .try_transfer(&funder, &env.current_contract_address(), &amount)
-            .map_err(|_| BoundlessError::TransferFailed)?
-            .map_err(|_| BoundlessError::TransferFailed)?;
-
-        let amount_u64 = amount as u64;
-        project.total_funded += amount_u64;
-
-        // Update the backers list
-        let mut found = false;
-        let mut updated_backers = Vec::new(&env);
-
-        let mut i = 0;
-        while i < project.backers.len() {
-            let (backer_address, backer_amount, token) = project.backers.get_unchecked(i);
-            if backer_address == funder && token == token_contract {
-                updated_backers.push_back((
-                    backer_address.clone(),
-                    backer_amount + amount_u64,
-                    token.clone(),
-                ));
-                found = true;
-            } else {
-                updated_backers.push_back((backer_address.clone(), backer_amount, token.clone()));
-            }
-            i += 1;
-        }
-
-        if !found {
-            updated_backers.push_back((funder.clone(), amount_u64, token_contract.clone()));
-        }
-        project.backers = updated_backers;
-
-        // Store the backer contribution
-        let backer_contribution = BackerContribution {
-            backer: funder.clone(),
-            amount: amount_u64,
-            token: token_contract.clone(),
-            timestamp: current_time,
-        };
-
-        let backers_key = DataKey::Backers(project_id.clone());
-        let mut backers: Vec<BackerContribution> = env
-            .storage()
-            .persistent()
-            .get(&backers_key)
-            .unwrap_or(Vec::new(&env));
-        backers.push_back(backer_contribution);
-
-        env.storage().persistent().set(&backers_key, &backers);
-
-        if project.total_funded >= project.funding_target {
-            project.status = ProjectStatus::Funded;
+        fund(
+            &env,
+            &project_id,
+            amount,
+            &payer,
+            &beneficiary,
+            &token_contract,
+            tier_id,
+        )?;
+        if payer != beneficiary {
+            env.events().publish(
+                ("project", "fund_for"),
+                (project_id, payer, beneficiary, amount),
+            );
         }
-
-        env.storage()
-            .persistent()
-            .set(&DataKey::Project(project_id.clone()), &project);
-
-        env.events().publish(
-            ("project", "funded"),
-            ProjectFundedEvent {
-                project_id: project_id.clone(),
-                total_funded: project.total_funded,
-            },
-        );
-
         Ok(())
     }
 
@@ -131,7 +75,17 @@ impl FundingOperations for BoundlessContract {
             .get(&DataKey::Project(project_id.clone()))
             .ok_or(BoundlessError::NotFound)?;
 
-        if project.status != ProjectStatus::Failed && !project.is_closed {
+        if project.status != ProjectStatus::Failed
+            && project.status != ProjectStatus::Cancelled
+            && !project.is_closed
+        {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        // Donations are final unless the project failed or was cancelled
+        if project.funding_model == FundingModel::Donation
+            && project.status != ProjectStatus::Failed
+            && project.status != ProjectStatus::Cancelled
+        {
             return Err(BoundlessError::InvalidOperation);
         }
 
@@ -170,33 +124,59 @@ impl FundingOperations for BoundlessContract {
             .map_err(|_| BoundlessError::BalanceCheckFailed)?
             .map_err(|_| BoundlessError::BalanceCheckFailed)?;
 
-        // Check if the contract has enough balance to refund
+        // Check if the contract holds the refund and the project's own escrow covers it,
+        // other projects' funds are off limits
         let mut refund_amount = 0_u64;
         let backer_contributions: Vec<BackerContribution> = env
             .storage()
             .persistent()
             .get(&DataKey::Backers(project_id.clone()))
             .unwrap_or(Vec::new(&env));
+        // Donations are claimed token by token, so only this token's unclaimed share is left
+        let (escrowed, funded) = if project.funding_model == FundingModel::Donation {
+            let funded = funded_by_token(&env, &project)
+                .get(token_contract.clone())
+                .unwrap_or(0);
+            let claimed: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::DonationsClaimed(
+                    project_id.clone(),
+                    token_contract.clone(),
+                ))
+                .unwrap_or(0);
+            (funded - claimed, funded)
+        } else {
+            (
+                project.total_funded - project.total_released - project.total_returned,
+                project.total_funded,
+            )
+        };
         let mut i = 0;
         while i < backer_contributions.len() {
             let backer_contribution = backer_contributions.get_unchecked(i);
             if backer_contribution.token == token_contract {
-                refund_amount += backer_contribution.amount;
+                refund_amount += pro_rata(backer_contribution.amount, escrowed, funded) as u64;
             }
             i += 1;
         }
 
-        if balance < refund_amount as i128 {
+        if balance < refund_amount as i128
+            || Self::get_escrow_balance(env.clone(), project_id.clone(), token_contract.clone())
+                < refund_amount
+        {
             return Err(BoundlessError::InsufficientFunds);
         }
 
         // Process refunds for each backer
+        let mut refunded = 0_u64;
         let mut i = 0;
         while i < backer_contributions.len() {
             let backer_contribution = backer_contributions.get_unchecked(i);
+            // Milestone payouts already left escrow, so each backer gets their share of the rest
             let (backer, amount, token, _) = (
                 backer_contribution.backer.clone(),
-                backer_contribution.amount as i128,
+                pro_rata(backer_contribution.amount, escrowed, funded),
                 backer_contribution.token.clone(),
                 backer_contribution.timestamp,
             );
@@ -208,6 +188,8 @@ impl FundingOperations for BoundlessContract {
 
             match token_client.try_transfer(&contract_address, &backer, &amount) {
                 Ok(_) => {
+                    debit_escrow(&env, &project_id, &token_contract, amount as u64)?;
+                    refunded += amount as u64;
                     env.events().publish(
                         ("project", "refund"),
                         RefundProcessedEvent {
@@ -227,6 +209,11 @@ impl FundingOperations for BoundlessContract {
             i += 1;
         }
 
+        add_to_total(
+            &env,
+            &DataKey::Refunded(project_id.clone(), token_contract.clone()),
+            refunded,
+        );
         refunded_tokens.push_back(token_contract);
 
         env.storage().persistent().set(
@@ -278,40 +265,1335 @@ impl FundingOperations for BoundlessContract {
         Ok(0)
     }
 
-    fn whitelist_token_contract(
+    fn claim_upfront(env: Env, project_id: String) -> Result<u64, BoundlessError> {
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        project.creator.require_auth();
+        if project.status != ProjectStatus::Funded && project.status != ProjectStatus::Completed {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        let tranche_key = DataKey::UpfrontTranche(project_id.clone());
+        let mut tranche: UpfrontTranche = env
+            .storage()
+            .persistent()
+            .get(&tranche_key)
+            .ok_or(BoundlessError::NotFound)?;
+        if tranche.claimed_at.is_some() {
+            return Err(BoundlessError::AlreadyClaimed);
+        }
+
+        // Counted in `total_released`, so refunds only cover what is left in escrow
+        let amount = pro_rata(
+            project.total_funded,
+            tranche.bps as u64,
+            BPS_DENOMINATOR as u64,
+        ) as u64;
+        release_to_creator(&env, &project, amount)?;
+        project.total_released += amount;
+        tranche.claimed_amount = amount;
+        tranche.claimed_at = Some(env.ledger().timestamp());
+
+        env.storage().persistent().set(&tranche_key, &tranche);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+        env.events()
+            .publish(("project", "upfront"), (project_id, amount));
+        Ok(amount)
+    }
+
+    fn get_upfront_tranche(env: Env, project_id: String) -> Result<UpfrontTranche, BoundlessError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UpfrontTranche(project_id))
+            .ok_or(BoundlessError::NotFound)
+    }
+
+    fn finalize_funding(env: Env, project_id: String) -> Result<ProjectStatus, BoundlessError> {
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        // Deferred pledges are settled through `settle_pledges` instead
+        if project.status != ProjectStatus::Funding || is_deferred(&env, &project_id) {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if env.ledger().timestamp() <= project.funding_deadline {
+            return Err(BoundlessError::InvalidOperation);
+        }
+
+        // Reaching the target already made the project `Funded`, so only
+        // projects that fell short get here
+        project.status = match project.funding_model {
+            FundingModel::AllOrNothing => ProjectStatus::Failed,
+            FundingModel::KeepItAll | FundingModel::Donation if project.total_funded > 0 => {
+                ProjectStatus::Funded
+            }
+            _ => ProjectStatus::Failed,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+        env.events().publish(
+            ("project", "fund_end"),
+            (project_id, project.status.clone()),
+        );
+        Ok(project.status)
+    }
+
+    fn set_deferred_pledges(
         env: Env,
-        admin: Address,
         project_id: String,
-        token_contract: Address,
+        creator: Address,
+        enabled: bool,
     ) -> Result<(), BoundlessError> {
-        admin.require_auth();
+        creator.require_auth();
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if project.creator != creator {
+            return Err(BoundlessError::Unauthorized);
+        }
+        if is_milestone_plan_locked(&project) || project.funding_model != FundingModel::AllOrNothing
+        {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::DeferredPledges(project_id), &enabled);
+        Ok(())
+    }
 
-        let admin_address: Address = env
+    fn settle_pledges(
+        env: Env,
+        project_id: String,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<Pledge>, BoundlessError> {
+        let mut project: Project = env
             .storage()
             .persistent()
-            .get(&DataKey::Admin)
+            .get(&DataKey::Project(project_id.clone()))
             .ok_or(BoundlessError::NotFound)?;
+        if project.status != ProjectStatus::Funding || !is_deferred(&env, &project_id) {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if env.ledger().timestamp() <= project.funding_deadline {
+            return Err(BoundlessError::InvalidOperation);
+        }
 
-        if admin_address != admin {
+        // Pages are settled in order, so no pledge is pulled twice
+        let settled_key = DataKey::PledgesSettled(project_id.clone());
+        let settled: u32 = env.storage().persistent().get(&settled_key).unwrap_or(0);
+        if start != settled {
+            return Err(BoundlessError::InvalidOperation);
+        }
+
+        let pledges = Self::get_pledges(env.clone(), project_id.clone())?;
+        let mut pledged = 0_u64;
+        let mut i = 0;
+        while i < pledges.len() {
+            pledged += pledges.get_unchecked(i).amount;
+            i += 1;
+        }
+
+        // Failed campaigns never take custody, so there is nothing to refund
+        let end = start
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(pledges.len());
+        let mut failed_pulls = Vec::new(&env);
+        let mut i = start;
+        while i < end {
+            let pledge = pledges.get_unchecked(i);
+            i += 1;
+            if pledged >= project.funding_target {
+                let pulled = if Self::is_token_non_standard(env.clone(), pledge.token.clone()) {
+                    Err(BoundlessError::InvalidTokenContract)
+                } else {
+                    receive_into_escrow(
+                        &env,
+                        &TokenClient::new(&env, &pledge.token),
+                        &pledge.backer,
+                        pledge.amount as i128,
+                        true,
+                    )
+                };
+                if let Ok(received) = pulled {
+                    record_contribution(
+                        &env,
+                        &project_id,
+                        &mut project,
+                        &pledge.backer,
+                        received,
+                        &pledge.token,
+                    )?;
+                    continue;
+                }
+                env.events().publish(
+                    ("project", "pull_fail"),
+                    (project_id.clone(), pledge.clone()),
+                );
+                failed_pulls.push_back(pledge.clone());
+            }
+            // Excluded pledges free up their reward tier
+            if let Some(tier_id) = pledge.tier_id {
+                release_reward_tier(&env, &project_id, &pledge.backer, tier_id);
+            }
+        }
+
+        if end < pledges.len() {
+            env.storage().persistent().set(&settled_key, &end);
+            env.storage()
+                .persistent()
+                .set(&DataKey::Project(project_id.clone()), &project);
+            return Ok(failed_pulls);
+        }
+
+        // Pulls that failed can still leave the campaign short of its target
+        if project.total_funded >= project.funding_target {
+            project.status = ProjectStatus::Funded;
+            unlock_stretch_goals(&env, &project_id, &mut project);
+        } else {
+            project.status = ProjectStatus::Failed;
+        }
+        env.storage().persistent().remove(&settled_key);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Pledges(project_id.clone()));
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+        env.events().publish(
+            ("project", "settled"),
+            (project_id, project.status, project.total_funded),
+        );
+        Ok(failed_pulls)
+    }
+
+    fn get_pledges(env: Env, project_id: String) -> Result<Vec<Pledge>, BoundlessError> {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Project(project_id.clone()))
+        {
+            return Err(BoundlessError::NotFound);
+        }
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pledges(project_id))
+            .unwrap_or(Vec::new(&env)))
+    }
+
+    fn attest_contribution(
+        env: Env,
+        attestor: Address,
+        project_id: String,
+        reference: String,
+        backer: Address,
+        amount: u64,
+    ) -> Result<bool, BoundlessError> {
+        attestor.require_auth();
+        if !Self::get_attestors(env.clone()).contains(&attestor) {
             return Err(BoundlessError::Unauthorized);
         }
+        let key = DataKey::Attestation(project_id.clone(), reference.clone());
+        // Anchors may resubmit, a known reference is acknowledged without counting twice
+        if env.storage().persistent().has(&key) {
+            return Ok(false);
+        }
+        if amount == 0 {
+            return Err(BoundlessError::InsufficientFunds);
+        }
 
-        let mut whitelisted_tokens: Vec<Address> = env
+        let mut project: Project = env
             .storage()
             .persistent()
-            .get(&DataKey::WhitelistedTokens(project_id.clone()))
-            .unwrap_or(Vec::new(&env));
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if project.status != ProjectStatus::Funding
+            && !(project.status == ProjectStatus::Funded
+                && has_locked_stretch_goal(&env, &project_id))
+        {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if project.is_closed {
+            return Err(BoundlessError::ProjectClosed);
+        }
+        if env.ledger().timestamp() > project.funding_deadline {
+            return Err(BoundlessError::FundingPeriodEnded);
+        }
+        // Deferred campaigns are settled from pledges alone
+        if is_deferred(&env, &project_id) {
+            return Err(BoundlessError::InvalidOperation);
+        }
 
-        if whitelisted_tokens.contains(&token_contract) {
-            return Err(BoundlessError::AlreadyWhitelisted);
+        project.total_funded += amount;
+        project.total_attested += amount;
+        let mut found = false;
+        let mut i = 0;
+        while i < project.attested_backers.len() {
+            let (address, attested) = project.attested_backers.get_unchecked(i);
+            if address == backer {
+                project
+                    .attested_backers
+                    .set(i, (address, attested + amount));
+                found = true;
+                break;
+            }
+            i += 1;
+        }
+        if !found {
+            project.attested_backers.push_back((backer.clone(), amount));
+        }
+        if project.total_funded >= project.funding_target {
+            project.status = ProjectStatus::Funded;
+            unlock_stretch_goals(&env, &project_id, &mut project);
         }
 
-        whitelisted_tokens.push_back(token_contract);
-        env.storage().persistent().set(
-            &DataKey::WhitelistedTokens(project_id.clone()),
-            &whitelisted_tokens,
-        );
+        let attestation = AttestedContribution {
+            reference,
+            backer,
+            amount,
+            attestor,
+            attested_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&key, &attestation);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+        env.events()
+            .publish(("project", "attested"), (project_id, attestation));
+        Ok(true)
+    }
+
+    fn get_attestation(
+        env: Env,
+        project_id: String,
+        reference: String,
+    ) -> Result<AttestedContribution, BoundlessError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Attestation(project_id, reference))
+            .ok_or(BoundlessError::NotFound)
+    }
+
+    fn get_funding_breakdown(env: Env, project_id: String) -> Result<(u64, u64), BoundlessError> {
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(BoundlessError::NotFound)?;
+        Ok((
+            project.total_funded - project.total_attested,
+            project.total_attested,
+        ))
+    }
+
+    fn create_recurring_pledge(
+        env: Env,
+        project_id: String,
+        backer: Address,
+        token_contract: Address,
+        amount: u64,
+        period: u64,
+    ) -> Result<(), BoundlessError> {
+        backer.require_auth();
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if !accepts_recurring_charges(&project) || is_deferred(&env, &project_id) {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if amount == 0 || period == 0 {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if !accepts_token(&env, &project_id, &token_contract) {
+            return Err(BoundlessError::InvalidTokenContract);
+        }
+        let key = DataKey::RecurringPledge(project_id.clone(), backer.clone());
+        let existing: Option<RecurringPledge> = env.storage().persistent().get(&key);
+        if existing.is_some_and(|pledge| pledge.active) {
+            return Err(BoundlessError::AlreadyExists);
+        }
+        if TokenClient::new(&env, &token_contract)
+            .allowance(&backer, &env.current_contract_address())
+            < amount as i128
+        {
+            return Err(BoundlessError::InsufficientFunds);
+        }
 
+        // The first charge is due straight away
+        let pledge = RecurringPledge {
+            backer,
+            token: token_contract,
+            amount,
+            period,
+            next_charge_at: env.ledger().timestamp(),
+            total_charged: 0,
+            active: true,
+        };
+        env.storage().persistent().set(&key, &pledge);
+        env.events()
+            .publish(("project", "recurring"), (project_id, pledge));
         Ok(())
     }
+
+    fn charge_recurring_pledge(
+        env: Env,
+        project_id: String,
+        backer: Address,
+    ) -> Result<u64, BoundlessError> {
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if !accepts_recurring_charges(&project) {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        let key = DataKey::RecurringPledge(project_id.clone(), backer.clone());
+        let mut pledge: RecurringPledge = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(BoundlessError::NotFound)?;
+        let current_time = env.ledger().timestamp();
+        if !pledge.active || current_time < pledge.next_charge_at {
+            return Err(BoundlessError::InvalidOperation);
+        }
+
+        if Self::is_token_non_standard(env.clone(), pledge.token.clone()) {
+            return Err(BoundlessError::InvalidTokenContract);
+        }
+        let received = receive_into_escrow(
+            &env,
+            &TokenClient::new(&env, &pledge.token),
+            &backer,
+            pledge.amount as i128,
+            true,
+        )?;
+        record_contribution(
+            &env,
+            &project_id,
+            &mut project,
+            &backer,
+            received,
+            &pledge.token,
+        )?;
+        if project.status == ProjectStatus::Funding
+            && project.total_funded >= project.funding_target
+        {
+            project.status = ProjectStatus::Funded;
+            unlock_stretch_goals(&env, &project_id, &mut project);
+        }
+
+        // Missed periods are skipped rather than charged in a burst
+        pledge.next_charge_at += pledge.period;
+        if pledge.next_charge_at <= current_time {
+            pledge.next_charge_at = current_time + pledge.period;
+        }
+        pledge.total_charged += received;
+        env.storage().persistent().set(&key, &pledge);
+
+        let revenue_key = DataKey::RecurringRevenue(project_id.clone());
+        let revenue: u64 = env.storage().persistent().get(&revenue_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&revenue_key, &(revenue + received));
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+        env.events()
+            .publish(("project", "charged"), (project_id, backer, received));
+        Ok(received)
+    }
+
+    fn cancel_recurring_pledge(
+        env: Env,
+        project_id: String,
+        backer: Address,
+    ) -> Result<(), BoundlessError> {
+        backer.require_auth();
+        let key = DataKey::RecurringPledge(project_id.clone(), backer.clone());
+        let mut pledge: RecurringPledge = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(BoundlessError::NotFound)?;
+        if !pledge.active {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        pledge.active = false;
+        env.storage().persistent().set(&key, &pledge);
+        env.events()
+            .publish(("project", "rec_cancel"), (project_id, backer));
+        Ok(())
+    }
+
+    fn get_recurring_pledge(
+        env: Env,
+        project_id: String,
+        backer: Address,
+    ) -> Result<RecurringPledge, BoundlessError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RecurringPledge(project_id, backer))
+            .ok_or(BoundlessError::NotFound)
+    }
+
+    fn get_recurring_revenue(env: Env, project_id: String) -> Result<u64, BoundlessError> {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Project(project_id.clone()))
+        {
+            return Err(BoundlessError::NotFound);
+        }
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::RecurringRevenue(project_id))
+            .unwrap_or(0))
+    }
+
+    fn claim_returned_funds(
+        env: Env,
+        project_id: String,
+        backer: Address,
+        token_contract: Address,
+    ) -> Result<u64, BoundlessError> {
+        backer.require_auth();
+        let amount = Self::get_claimable_returns(
+            env.clone(),
+            project_id.clone(),
+            backer.clone(),
+            token_contract.clone(),
+        )?;
+        if amount == 0 {
+            return Err(BoundlessError::InsufficientFunds);
+        }
+
+        debit_escrow(&env, &project_id, &token_contract, amount)?;
+        TokenClient::new(&env, &token_contract)
+            .try_transfer(&env.current_contract_address(), &backer, &(amount as i128))
+            .map_err(|_| BoundlessError::TransferFailed)?
+            .map_err(|_| BoundlessError::TransferFailed)?;
+        let claimed_key =
+            DataKey::ReturnsClaimed(project_id.clone(), backer.clone(), token_contract);
+        let claimed: u64 = env.storage().persistent().get(&claimed_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&claimed_key, &(claimed + amount));
+        env.events().publish(
+            ("project", "refund"),
+            RefundProcessedEvent {
+                project_id,
+                backer,
+                amount,
+            },
+        );
+        Ok(amount)
+    }
+
+    fn get_claimable_returns(
+        env: Env,
+        project_id: String,
+        backer: Address,
+        token_contract: Address,
+    ) -> Result<u64, BoundlessError> {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Project(project_id.clone()))
+        {
+            return Err(BoundlessError::NotFound);
+        }
+        let returns: Vec<ReturnedFunds> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReturnedFunds(project_id.clone()))
+            .unwrap_or(Vec::new(&env));
+        if returns.is_empty() {
+            return Ok(0);
+        }
+        let contributions: Vec<BackerContribution> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Backers(project_id.clone()))
+            .unwrap_or(Vec::new(&env));
+
+        // Each return is shared by the contributions made before it, against
+        // the funding at that time, so later backers never dilute it
+        let mut share = 0_u64;
+        let mut contributed = 0_u64;
+        let mut next = 0;
+        let mut i = 0;
+        while i < returns.len() {
+            let returned = returns.get_unchecked(i);
+            while next < returned.contributions.min(contributions.len()) {
+                let contribution = contributions.get_unchecked(next);
+                if contribution.backer == backer && contribution.token == token_contract {
+                    contributed += contribution.amount;
+                }
+                next += 1;
+            }
+            share += pro_rata(contributed, returned.amount, returned.total_funded) as u64;
+            i += 1;
+        }
+        let claimed: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReturnsClaimed(project_id, backer, token_contract))
+            .unwrap_or(0);
+        Ok(share - claimed)
+    }
+
+    fn claim_donations(
+        env: Env,
+        project_id: String,
+        token_contract: Address,
+    ) -> Result<u64, BoundlessError> {
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        project.creator.require_auth();
+        if project.funding_model != FundingModel::Donation {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if project.status != ProjectStatus::Funding && project.status != ProjectStatus::Funded {
+            return Err(BoundlessError::InvalidOperation);
+        }
+
+        // Donations arrive over time, so they are claimed token by token
+        let claimed_key = DataKey::DonationsClaimed(project_id.clone(), token_contract.clone());
+        let claimed: u64 = env.storage().persistent().get(&claimed_key).unwrap_or(0);
+        let funded = funded_by_token(&env, &project)
+            .get(token_contract.clone())
+            .unwrap_or(0);
+        let amount = funded - claimed;
+        if amount == 0 {
+            return Err(BoundlessError::InsufficientFunds);
+        }
+
+        let fee = pro_rata(
+            amount,
+            Self::get_config(env.clone()).donation_fee_bps as u64,
+            BPS_DENOMINATOR as u64,
+        );
+        debit_escrow(&env, &project_id, &token_contract, amount)?;
+        add_to_total(
+            &env,
+            &DataKey::Released(project_id.clone(), token_contract.clone()),
+            amount,
+        );
+        let token_client = TokenClient::new(&env, &token_contract);
+        if fee > 0 {
+            token_client
+                .try_transfer(
+                    &env.current_contract_address(),
+                    &Self::get_admin(&env),
+                    &fee,
+                )
+                .map_err(|_| BoundlessError::TransferFailed)?
+                .map_err(|_| BoundlessError::TransferFailed)?;
+        }
+        let net = amount as i128 - fee;
+        pay_to_splits(&env, &token_client, &payout_splits(&env, &project), net)?;
+
+        project.total_released += amount;
+        env.storage().persistent().set(&claimed_key, &funded);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+        env.events()
+            .publish(("project", "donations"), (project_id, token_contract, net));
+        Ok(net as u64)
+    }
+
+    fn add_reward_tier(
+        env: Env,
+        project_id: String,
+        creator: Address,
+        title: String,
+        metadata_uri: String,
+        min_pledge: u64,
+        prices: Vec<TierPrice>,
+        quantity: Option<u32>,
+    ) -> Result<u32, BoundlessError> {
+        creator.require_auth();
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if project.creator != creator {
+            return Err(BoundlessError::Unauthorized);
+        }
+        // Tiers are part of what backers are asked to fund
+        if is_milestone_plan_locked(&project) {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if min_pledge == 0 || quantity == Some(0) {
+            return Err(BoundlessError::InvalidOperation);
+        }
+
+        let mut tiers: Vec<RewardTier> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RewardTiers(project_id.clone()))
+            .unwrap_or(Vec::new(&env));
+        let tier = RewardTier {
+            id: tiers.len(),
+            title,
+            metadata_uri,
+            min_pledge,
+            prices,
+            quantity,
+            claimed: 0,
+        };
+        tiers.push_back(tier.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::RewardTiers(project_id.clone()), &tiers);
+        env.events().publish(("project", "tier"), tier.clone());
+        Ok(tier.id)
+    }
+
+    fn get_reward_tiers(env: Env, project_id: String) -> Result<Vec<RewardTier>, BoundlessError> {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Project(project_id.clone()))
+        {
+            return Err(BoundlessError::NotFound);
+        }
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::RewardTiers(project_id))
+            .unwrap_or(Vec::new(&env)))
+    }
+
+    fn get_tier_remaining(
+        env: Env,
+        project_id: String,
+        tier_id: u32,
+    ) -> Result<Option<u32>, BoundlessError> {
+        let tier = Self::get_reward_tiers(env, project_id)?
+            .get(tier_id)
+            .ok_or(BoundlessError::TierNotFound)?;
+        Ok(tier.quantity.map(|quantity| quantity - tier.claimed))
+    }
+
+    fn get_backer_tiers(
+        env: Env,
+        project_id: String,
+        backer: Address,
+    ) -> Result<Vec<u32>, BoundlessError> {
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::BackerTiers(project_id, backer))
+            .unwrap_or(Vec::new(&env)))
+    }
+
+    fn whitelist_token_contract(
+        env: Env,
+        admin: Address,
+        project_id: String,
+        token_contract: Address,
+    ) -> Result<(), BoundlessError> {
+        admin.require_auth();
+
+        let admin_address: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(BoundlessError::NotFound)?;
+
+        if admin_address != admin {
+            return Err(BoundlessError::Unauthorized);
+        }
+
+        let mut whitelisted_tokens: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WhitelistedTokens(project_id.clone()))
+            .unwrap_or(Vec::new(&env));
+
+        if whitelisted_tokens.contains(&token_contract) {
+            return Err(BoundlessError::AlreadyWhitelisted);
+        }
+        if Self::is_token_non_standard(env.clone(), token_contract.clone()) {
+            return Err(BoundlessError::InvalidTokenContract);
+        }
+
+        whitelisted_tokens.push_back(token_contract);
+        env.storage().persistent().set(
+            &DataKey::WhitelistedTokens(project_id.clone()),
+            &whitelisted_tokens,
+        );
+
+        Ok(())
+    }
+
+    fn get_escrow_balance(env: Env, project_id: String, token_contract: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Escrow(project_id, token_contract))
+            .unwrap_or(0)
+    }
+
+    fn set_token_non_standard(
+        env: Env,
+        admin: Address,
+        token_contract: Address,
+        non_standard: bool,
+    ) -> Result<(), BoundlessError> {
+        admin.require_auth();
+        if Self::get_admin(&env) != admin {
+            return Err(BoundlessError::Unauthorized);
+        }
+        let mut flagged: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::NonStandardTokens)
+            .unwrap_or(Vec::new(&env));
+        match (flagged.first_index_of(&token_contract), non_standard) {
+            (None, true) => flagged.push_back(token_contract),
+            (Some(index), false) => {
+                flagged.remove(index);
+            }
+            _ => return Ok(()),
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::NonStandardTokens, &flagged);
+        Ok(())
+    }
+
+    fn is_token_non_standard(env: Env, token_contract: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get::<_, Vec<Address>>(&DataKey::NonStandardTokens)
+            .is_some_and(|flagged| flagged.contains(&token_contract))
+    }
+}
+
+/// Takes `amount` from `payer` and credits it to `beneficiary`, who holds the
+/// resulting refund rights, voting weight and reward tiers.
+fn fund(
+    env: &Env,
+    project_id: &String,
+    amount: i128,
+    payer: &Address,
+    beneficiary: &Address,
+    token_contract: &Address,
+    tier_id: Option<u32>,
+) -> Result<(), BoundlessError> {
+    if amount <= 0 {
+        return Err(BoundlessError::InsufficientFunds);
+    }
+    if amount > u64::MAX as i128 {
+        return Err(BoundlessError::AmountTooLarge);
+    }
+
+    let mut project: Project = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Project(project_id.clone()))
+        .ok_or(BoundlessError::NotFound)?;
+
+    // Funded projects keep accepting funds while a stretch goal is locked
+    if project.status != ProjectStatus::Funding
+        && !(project.status == ProjectStatus::Funded && has_locked_stretch_goal(env, project_id))
+    {
+        return Err(BoundlessError::InvalidOperation);
+    }
+
+    if project.is_closed {
+        return Err(BoundlessError::ProjectClosed);
+    }
+
+    let current_time = env.ledger().timestamp();
+
+    if current_time > project.funding_deadline {
+        return Err(BoundlessError::FundingPeriodEnded);
+    }
+
+    if !accepts_token(env, project_id, token_contract) {
+        return Err(BoundlessError::InvalidTokenContract);
+    }
+
+    let token_client = TokenClient::new(env, token_contract);
+    if is_deferred(env, project_id) {
+        if let Some(tier_id) = tier_id {
+            claim_reward_tier(
+                env,
+                project_id,
+                beneficiary,
+                tier_id,
+                amount,
+                token_contract,
+            )?;
+        }
+        return pledge(env, project_id, beneficiary, amount, &token_client, tier_id);
+    }
+    let received = receive_into_escrow(env, &token_client, payer, amount, false)?;
+
+    // Tiers are priced against what actually arrived
+    if let Some(tier_id) = tier_id {
+        claim_reward_tier(
+            env,
+            project_id,
+            beneficiary,
+            tier_id,
+            received as i128,
+            token_contract,
+        )?;
+    }
+
+    record_contribution(
+        env,
+        project_id,
+        &mut project,
+        beneficiary,
+        received,
+        token_contract,
+    )?;
+
+    if project.total_funded >= project.funding_target {
+        project.status = ProjectStatus::Funded;
+        unlock_stretch_goals(env, project_id, &mut project);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Project(project_id.clone()), &project);
+
+    env.events().publish(
+        ("project", "funded"),
+        ProjectFundedEvent {
+            project_id: project_id.clone(),
+            total_funded: project.total_funded,
+        },
+    );
+
+    Ok(())
+}
+
+/// Books a contribution that has reached the contract: adds it to the
+/// project's totals and backers and to the contribution history.
+fn record_contribution(
+    env: &Env,
+    project_id: &String,
+    project: &mut Project,
+    funder: &Address,
+    amount_u64: u64,
+    token_contract: &Address,
+) -> Result<(), BoundlessError> {
+    project.total_funded += amount_u64;
+    credit_escrow(env, project_id, token_contract, amount_u64)?;
+
+    // Update the backers list
+    let mut found = false;
+    let mut updated_backers = Vec::new(env);
+
+    let mut i = 0;
+    while i < project.backers.len() {
+        let (backer_address, backer_amount, token) = project.backers.get_unchecked(i);
+        if backer_address == *funder && token == *token_contract {
+            updated_backers.push_back((
+                backer_address.clone(),
+                backer_amount + amount_u64,
+                token.clone(),
+            ));
+            found = true;
+        } else {
+            updated_backers.push_back((backer_address.clone(), backer_amount, token.clone()));
+        }
+        i += 1;
+    }
+
+    if !found {
+        updated_backers.push_back((funder.clone(), amount_u64, token_contract.clone()));
+    }
+    project.backers = updated_backers;
+
+    // Store the backer contribution
+    let backer_contribution = BackerContribution {
+        backer: funder.clone(),
+        amount: amount_u64,
+        token: token_contract.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+
+    let backers_key = DataKey::Backers(project_id.clone());
+    let mut backers: Vec<BackerContribution> = env
+        .storage()
+        .persistent()
+        .get(&backers_key)
+        .unwrap_or(Vec::new(env));
+    backers.push_back(backer_contribution);
+
+    env.storage().persistent().set(&backers_key, &backers);
+    Ok(())
+}
+
+/// Credits tokens the contract just received to the project's escrow ledger.
+/// Ledgers across all projects may never exceed what the contract holds.
+fn credit_escrow(
+    env: &Env,
+    project_id: &String,
+    token_contract: &Address,
+    amount: u64,
+) -> Result<(), BoundlessError> {
+    let key = DataKey::Escrow(project_id.clone(), token_contract.clone());
+    let escrowed: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+    let total_key = DataKey::EscrowTotal(token_contract.clone());
+    let total: u64 = env.storage().persistent().get(&total_key).unwrap_or(0);
+    let balance = TokenClient::new(env, token_contract)
+        .try_balance(&env.current_contract_address())
+        .map_err(|_| BoundlessError::BalanceCheckFailed)?
+        .map_err(|_| BoundlessError::BalanceCheckFailed)?;
+    if (total + amount) as i128 > balance {
+        return Err(BoundlessError::EscrowExceedsBalance);
+    }
+    env.storage().persistent().set(&key, &(escrowed + amount));
+    env.storage()
+        .persistent()
+        .set(&total_key, &(total + amount));
+    Ok(())
+}
+
+/// Debits tokens leaving the contract from the project's escrow ledger, so a
+/// project can never pay out funds held for another.
+pub(crate) fn debit_escrow(
+    env: &Env,
+    project_id: &String,
+    token_contract: &Address,
+    amount: u64,
+) -> Result<(), BoundlessError> {
+    let key = DataKey::Escrow(project_id.clone(), token_contract.clone());
+    let escrowed: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+    if escrowed < amount {
+        return Err(BoundlessError::InsufficientFunds);
+    }
+    let total_key = DataKey::EscrowTotal(token_contract.clone());
+    let total: u64 = env.storage().persistent().get(&total_key).unwrap_or(0);
+    env.storage().persistent().set(&key, &(escrowed - amount));
+    env.storage()
+        .persistent()
+        .set(&total_key, &(total - amount));
+    Ok(())
+}
+
+/// Whether the project takes funding in the token: it must be on the project's
+/// allowlist and not flagged as non-standard.
+fn accepts_token(env: &Env, project_id: &String, token_contract: &Address) -> bool {
+    let whitelisted_tokens: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::WhitelistedTokens(project_id.clone()))
+        .unwrap_or(Vec::new(env));
+    whitelisted_tokens.contains(token_contract)
+        && !BoundlessContract::is_token_non_standard(env.clone(), token_contract.clone())
+}
+
+/// Moves `amount` from `from` into the contract, directly or through the
+/// allowance `from` granted the contract, and returns what actually arrived.
+/// Fee-on-transfer and rebasing tokens can deliver less than requested.
+fn receive_into_escrow(
+    env: &Env,
+    token_client: &TokenClient,
+    from: &Address,
+    amount: i128,
+    via_allowance: bool,
+) -> Result<u64, BoundlessError> {
+    let contract_address = env.current_contract_address();
+    let balance_before = token_client
+        .try_balance(&contract_address)
+        .map_err(|_| BoundlessError::BalanceCheckFailed)?
+        .map_err(|_| BoundlessError::BalanceCheckFailed)?;
+    if via_allowance {
+        token_client
+            .try_transfer_from(&contract_address, from, &contract_address, &amount)
+            .map_err(|_| BoundlessError::TransferFailed)?
+            .map_err(|_| BoundlessError::TransferFailed)?;
+    } else {
+        token_client
+            .try_transfer(from, &contract_address, &amount)
+            .map_err(|_| BoundlessError::TransferFailed)?
+            .map_err(|_| BoundlessError::TransferFailed)?;
+    }
+    let balance_after = token_client
+        .try_balance(&contract_address)
+        .map_err(|_| BoundlessError::BalanceCheckFailed)?
+        .map_err(|_| BoundlessError::BalanceCheckFailed)?;
+    let received = balance_after - balance_before;
+    if received <= 0 {
+        return Err(BoundlessError::TransferFailed);
+    }
+    u64::try_from(received).map_err(|_| BoundlessError::AmountTooLarge)
+}
+
+/// Recurring charges keep flowing while a donation project is raising or
+/// delivering. Other models have no way to release funds beyond their
+/// milestone plan.
+fn accepts_recurring_charges(project: &Project) -> bool {
+    project.funding_model == FundingModel::Donation
+        && !project.is_closed
+        && (project.status == ProjectStatus::Funding || project.status == ProjectStatus::Funded)
+}
+
+/// Whether the project takes deferred pledges instead of immediate transfers.
+fn is_deferred(env: &Env, project_id: &String) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DeferredPledges(project_id.clone()))
+        .unwrap_or(false)
+}
+
+/// Amount pledged but not yet settled, by one backer or by everyone. Settled
+/// pledges are already counted in the project's contributions.
+pub(crate) fn unsettled_pledges(env: &Env, project_id: &String, backer: Option<&Address>) -> u64 {
+    let pledges: Vec<Pledge> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Pledges(project_id.clone()))
+        .unwrap_or(Vec::new(env));
+    let mut total = 0_u64;
+    let mut i: u32 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::PledgesSettled(project_id.clone()))
+        .unwrap_or(0);
+    while i < pledges.len() {
+        let pledge = pledges.get_unchecked(i);
+        if backer.is_none_or(|backer| pledge.backer == *backer) {
+            total += pledge.amount;
+        }
+        i += 1;
+    }
+    total
+}
+
+/// Records a pledge backed by the backer's allowance to the contract. The
+/// allowance has to cover every pledge the backer has open in the token.
+fn pledge(
+    env: &Env,
+    project_id: &String,
+    backer: &Address,
+    amount: i128,
+    token_client: &TokenClient,
+    tier_id: Option<u32>,
+) -> Result<(), BoundlessError> {
+    let mut pledges: Vec<Pledge> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Pledges(project_id.clone()))
+        .unwrap_or(Vec::new(env));
+    let mut pledged = amount;
+    let mut i = 0;
+    while i < pledges.len() {
+        let existing = pledges.get_unchecked(i);
+        if existing.backer == *backer && existing.token == token_client.address {
+            pledged += existing.amount as i128;
+        }
+        i += 1;
+    }
+    if token_client.allowance(backer, &env.current_contract_address()) < pledged {
+        return Err(BoundlessError::InsufficientFunds);
+    }
+
+    let pledge = Pledge {
+        backer: backer.clone(),
+        amount: u64::try_from(amount).map_err(|_| BoundlessError::AmountTooLarge)?,
+        token: token_client.address.clone(),
+        pledged_at: env.ledger().timestamp(),
+        tier_id,
+    };
+    pledges.push_back(pledge.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::Pledges(project_id.clone()), &pledges);
+    env.events()
+        .publish(("project", "pledged"), (project_id.clone(), pledge));
+    Ok(())
+}
+
+/// Checks a pledge against a reward tier and takes one unit of the tier for
+/// the backer.
+fn claim_reward_tier(
+    env: &Env,
+    project_id: &String,
+    backer: &Address,
+    tier_id: u32,
+    amount: i128,
+    token: &Address,
+) -> Result<(), BoundlessError> {
+    let mut tiers: Vec<RewardTier> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RewardTiers(project_id.clone()))
+        .unwrap_or(Vec::new(env));
+    let mut tier = tiers.get(tier_id).ok_or(BoundlessError::TierNotFound)?;
+    if let Some(quantity) = tier.quantity {
+        if tier.claimed >= quantity {
+            return Err(BoundlessError::TierSoldOut);
+        }
+    }
+
+    let mut min_amount = tier.min_pledge;
+    let mut i = 0;
+    while i < tier.prices.len() {
+        let price = tier.prices.get_unchecked(i);
+        if price.token == *token {
+            min_amount = price.min_amount;
+            break;
+        }
+        i += 1;
+    }
+    if amount < min_amount as i128 {
+        return Err(BoundlessError::PledgeBelowTierMinimum);
+    }
+
+    tier.claimed += 1;
+    tiers.set(tier_id, tier);
+    env.storage()
+        .persistent()
+        .set(&DataKey::RewardTiers(project_id.clone()), &tiers);
+
+    let key = DataKey::BackerTiers(project_id.clone(), backer.clone());
+    let mut backer_tiers: Vec<u32> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    backer_tiers.push_back(tier_id);
+    env.storage().persistent().set(&key, &backer_tiers);
+    Ok(())
+}
+
+/// Gives back the reward tier unit taken by a pledge that was not settled.
+fn release_reward_tier(env: &Env, project_id: &String, backer: &Address, tier_id: u32) {
+    let mut tiers: Vec<RewardTier> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RewardTiers(project_id.clone()))
+        .unwrap_or(Vec::new(env));
+    if let Some(mut tier) = tiers.get(tier_id) {
+        tier.claimed = tier.claimed.saturating_sub(1);
+        tiers.set(tier_id, tier);
+        env.storage()
+            .persistent()
+            .set(&DataKey::RewardTiers(project_id.clone()), &tiers);
+    }
+
+    let key = DataKey::BackerTiers(project_id.clone(), backer.clone());
+    let mut backer_tiers: Vec<u32> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    if let Some(index) = backer_tiers.first_index_of(tier_id) {
+        backer_tiers.remove(index);
+        env.storage().persistent().set(&key, &backer_tiers);
+    }
+}
+
+/// `amount * numerator / denominator`, computed without overflowing `u64`.
+pub(crate) fn pro_rata(amount: u64, numerator: u64, denominator: u64) -> i128 {
+    if denominator == 0 {
+        return 0;
+    }
+    amount as i128 * numerator as i128 / denominator as i128
+}
+
+/// Amount funded in each token, used to split escrow movements across tokens.
+pub(crate) fn funded_by_token(env: &Env, project: &Project) -> Map<Address, u64> {
+    let mut totals: Map<Address, u64> = Map::new(env);
+    let mut i = 0;
+    while i < project.backers.len() {
+        let (_, amount, token) = project.backers.get_unchecked(i);
+        let total = totals.get(token.clone()).unwrap_or(0);
+        totals.set(token, total + amount);
+        i += 1;
+    }
+    totals
+}
+
+/// Pays `amount` of the project's escrow to the creator side, split across
+/// tokens in proportion to how the project was funded and across the
+/// project's payout recipients by their shares.
+pub(crate) fn release_to_creator(
+    env: &Env,
+    project: &Project,
+    amount: u64,
+) -> Result<(), BoundlessError> {
+    let splits = payout_splits(env, project);
+    for (token, funded) in funded_by_token(env, project).iter() {
+        let share = pro_rata(funded, amount, project.total_funded);
+        debit_escrow(env, &project.project_id, &token, share as u64)?;
+        add_to_total(
+            env,
+            &DataKey::Released(project.project_id.clone(), token.clone()),
+            share as u64,
+        );
+        pay_to_splits(env, &TokenClient::new(env, &token), &splits, share)?;
+    }
+    Ok(())
+}
+
+/// Adds `amount` to a running per-project total kept for the audits.
+fn add_to_total(env: &Env, key: &DataKey, amount: u64) {
+    if amount == 0 {
+        return;
+    }
+    let total: u64 = env.storage().persistent().get(key).unwrap_or(0);
+    env.storage().persistent().set(key, &(total + amount));
+}
+
+/// Pays `amount` of one token to the payout recipients by their shares.
+fn pay_to_splits(
+    env: &Env,
+    token_client: &TokenClient,
+    splits: &Vec<PayoutSplit>,
+    amount: i128,
+) -> Result<(), BoundlessError> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let contract_address = env.current_contract_address();
+    let mut remaining = amount;
+    let mut i = 0;
+    while i < splits.len() {
+        let split = splits.get_unchecked(i);
+        // The last recipient also takes the rounding dust
+        let part = if i + 1 == splits.len() {
+            remaining
+        } else {
+            amount * split.bps as i128 / BPS_DENOMINATOR as i128
+        };
+        if part > 0 {
+            token_client
+                .try_transfer(&contract_address, &split.recipient, &part)
+                .map_err(|_| BoundlessError::TransferFailed)?
+                .map_err(|_| BoundlessError::TransferFailed)?;
+        }
+        remaining -= part;
+        i += 1;
+    }
+    Ok(())
 }
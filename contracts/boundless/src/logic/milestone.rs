@@ -1,12 +1,155 @@
 use crate::{
-    datatypes::{BoundlessError, DataKey, Milestone, MilestoneStatus, Project, ProjectStatus},
-    interface::MilestoneOperations,
+    datatypes::{
+        BackerContribution, BoundlessError, DataKey, FundingModel, Milestone, MilestoneAmendment,
+        MilestoneApprovedEvent, MilestoneEvidence, MilestonePayout, MilestonePlanItem,
+        MilestoneRejectedEvent, MilestoneReview, MilestoneStatus, OverdueVote, Project,
+        ProjectStatus, RemainderAction, ReturnedFunds, ReviewReason, StretchGoal, TeamAction,
+        UpfrontTranche, VestingStream, AMENDMENT_VOTING_PERIOD_SECONDS, BPS_DENOMINATOR,
+        MILESTONE_EXTENSION_SECONDS, MILESTONE_GRACE_PERIOD_SECONDS, OVERDUE_VOTING_PERIOD_SECONDS,
+    },
+    interface::{ContractManagement, MilestoneOperations},
+    logic::{
+        funding::{pro_rata, release_to_creator},
+        project::{cancel_project, is_milestone_plan_locked, return_bond},
+        team::approve_team_action,
+        voting::contribution_weight,
+    },
     BoundlessContract, BoundlessContractArgs, BoundlessContractClient,
 };
-use soroban_sdk::{contractimpl, symbol_short, Address, Env, String, Vec};
+use soroban_sdk::{contractimpl, symbol_short, Address, BytesN, Env, String, Vec};
 
 #[contractimpl]
 impl MilestoneOperations for BoundlessContract {
+    fn add_milestone(
+        env: Env,
+        project_id: String,
+        creator: Address,
+        description: String,
+        amount: u64,
+        due_date: u64,
+    ) -> Result<u32, BoundlessError> {
+        creator.require_auth();
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if project.is_closed {
+            return Err(BoundlessError::ProjectClosed);
+        }
+        if is_milestone_plan_locked(&project) {
+            return Err(BoundlessError::MilestonePlanLocked);
+        }
+        if project.funding_model == FundingModel::Donation {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if project.milestones.len() >= project.milestone_count {
+            return Err(BoundlessError::InvalidMilestone);
+        }
+        if amount == 0 || due_date <= env.ledger().timestamp() {
+            return Err(BoundlessError::InvalidMilestone);
+        }
+
+        // Until the team approves, this is the index the milestone will take
+        let milestone_number = project.milestones.len();
+        if !approve_team_action(
+            &env,
+            &project_id,
+            &project,
+            &creator,
+            TeamAction::AddMilestone(description.clone(), amount, due_date),
+        )? {
+            return Ok(milestone_number);
+        }
+        project.milestones.push_back(Milestone {
+            number: milestone_number,
+            description,
+            amount,
+            status: MilestoneStatus::Pending,
+            released_at: None,
+            completed_at: None,
+            due_date,
+            paid_amount: 0,
+            returned_amount: 0,
+            held_amount: 0,
+            rolled_over_to: None,
+            evidence: Vec::new(&env),
+            reviews: Vec::new(&env),
+        });
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+        Ok(milestone_number)
+    }
+    fn submit_milestone(
+        env: Env,
+        project_id: String,
+        milestone_number: u32,
+        creator: Address,
+        evidence_uri: String,
+        evidence_hash: BytesN<32>,
+    ) -> Result<(), BoundlessError> {
+        creator.require_auth();
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if project.creator != creator {
+            return Err(BoundlessError::Unauthorized);
+        }
+        if project.is_closed {
+            return Err(BoundlessError::ProjectClosed);
+        }
+        // Keep-it-all projects that fell short are also `Funded` once finalized
+        if project.status != ProjectStatus::Funded {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if project.milestones.len() <= milestone_number {
+            return Err(BoundlessError::InvalidOperation);
+        }
+
+        let milestone = project.milestones.get_unchecked(milestone_number);
+        match milestone.status {
+            MilestoneStatus::Released => return Err(BoundlessError::MilestoneAlreadyReleased),
+            MilestoneStatus::Approved => return Err(BoundlessError::MilestoneAlreadyApproved),
+            MilestoneStatus::Overdue => return Err(BoundlessError::InvalidOperation),
+            // A rejected milestone can be resubmitted with new evidence
+            MilestoneStatus::Pending | MilestoneStatus::Rejected => {}
+        }
+
+        let current_time = env.ledger().timestamp();
+        let mut evidence = milestone.evidence.clone();
+        evidence.push_back(MilestoneEvidence {
+            uri: evidence_uri,
+            hash: evidence_hash,
+            submitted_at: current_time,
+        });
+        let updated_milestone = Milestone {
+            status: MilestoneStatus::Released,
+            released_at: Some(current_time),
+            completed_at: None,
+            evidence,
+            ..milestone
+        };
+        project.milestones.set(milestone_number, updated_milestone);
+        project
+            .milestone_releases
+            .push_back((milestone_number, current_time));
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+        env.events().publish(
+            (
+                DataKey::Project(project_id.clone()),
+                symbol_short!("submitted"),
+            ),
+            milestone_number,
+        );
+        Ok(())
+    }
     fn release_milestone(
         env: Env,
         project_id: String,
@@ -27,7 +170,7 @@ impl MilestoneOperations for BoundlessContract {
             .persistent()
             .get(&DataKey::Project(project_id.clone()))
             .ok_or(BoundlessError::NotFound)?;
-        if project.status == ProjectStatus::Voting || project.status == ProjectStatus::Funding {
+        if project.status != ProjectStatus::Funded {
             return Err(BoundlessError::InvalidOperation);
         }
         if project.creator == admin {
@@ -68,67 +211,349 @@ impl MilestoneOperations for BoundlessContract {
         project_id: String,
         milestone_number: u32,
         admin: Address,
+        reason: ReviewReason,
+        feedback_uri: String,
+        feedback_hash: BytesN<32>,
+    ) -> Result<(), BoundlessError> {
+        admin.require_auth();
+        let review = MilestoneReview {
+            reviewer: admin,
+            approved: true,
+            reason,
+            feedback_uri,
+            feedback_hash,
+            reviewed_at: env.ledger().timestamp(),
+        };
+        approve(
+            &env,
+            project_id,
+            milestone_number,
+            BPS_DENOMINATOR,
+            RemainderAction::Refund,
+            review,
+        )
+    }
+    fn approve_milestone_partial(
+        env: Env,
+        project_id: String,
+        milestone_number: u32,
+        admin: Address,
+        approved_bps: u32,
+        remainder: RemainderAction,
+        reason: ReviewReason,
+        feedback_uri: String,
+        feedback_hash: BytesN<32>,
     ) -> Result<(), BoundlessError> {
         admin.require_auth();
+        let review = MilestoneReview {
+            reviewer: admin,
+            approved: true,
+            reason,
+            feedback_uri,
+            feedback_hash,
+            reviewed_at: env.ledger().timestamp(),
+        };
+        approve(
+            &env,
+            project_id,
+            milestone_number,
+            approved_bps,
+            remainder,
+            review,
+        )
+    }
+    fn challenge_milestone(
+        env: Env,
+        project_id: String,
+        milestone_number: u32,
+        backer: Address,
+    ) -> Result<(), BoundlessError> {
+        backer.require_auth();
         let mut project: Project = env
             .storage()
             .persistent()
             .get(&DataKey::Project(project_id.clone()))
             .ok_or(BoundlessError::NotFound)?;
-        if project.status == ProjectStatus::Voting || project.status == ProjectStatus::Funding {
+        if project.status != ProjectStatus::Funded {
             return Err(BoundlessError::InvalidOperation);
         }
-        if project.creator == admin {
-            return Err(BoundlessError::InvalidOperation);
+        let payout_key = DataKey::MilestonePayout(project_id.clone(), milestone_number);
+        let mut payout: MilestonePayout = env
+            .storage()
+            .persistent()
+            .get(&payout_key)
+            .ok_or(BoundlessError::NotFound)?;
+
+        if env.ledger().timestamp() >= payout.claimable_at {
+            return Err(BoundlessError::ChallengePeriodEnded);
         }
-        if project.milestones.len() <= milestone_number {
-            return Err(BoundlessError::InvalidOperation);
+        if payout.challengers.contains(&backer) {
+            return Err(BoundlessError::AlreadyVoted);
+        }
+        let weight = contribution_weight(&project, &backer);
+        if weight == 0 {
+            return Err(BoundlessError::NoBackerContributions);
+        }
+        payout.challenge_weight += weight;
+        payout.challengers.push_back(backer);
+
+        let config = Self::get_config(env.clone());
+        let threshold = pro_rata(
+            project.total_funded,
+            config.challenge_threshold_bps as u64,
+            BPS_DENOMINATOR as u64,
+        ) as u64;
+        if payout.challenge_weight < threshold {
+            env.storage().persistent().set(&payout_key, &payout);
+            return Ok(());
         }
 
+        // Enough weight objected, so the approval is voided and the milestone goes back to review
         let milestone = project.milestones.get_unchecked(milestone_number);
-        if milestone.status != MilestoneStatus::Released {
+        project.milestones.set(
+            milestone_number,
+            Milestone {
+                status: MilestoneStatus::Released,
+                completed_at: None,
+                ..milestone
+            },
+        );
+        if let Some(index) = project
+            .milestone_approvals
+            .last_index_of((milestone_number, true))
+        {
+            project.milestone_approvals.remove(index);
+        }
+        env.storage().persistent().remove(&payout_key);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+        env.events().publish(
+            (
+                DataKey::Project(project_id.clone()),
+                symbol_short!("challenge"),
+            ),
+            milestone_number,
+        );
+        Ok(())
+    }
+    fn claim_milestone_payout(
+        env: Env,
+        project_id: String,
+        milestone_number: u32,
+    ) -> Result<(), BoundlessError> {
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        project.creator.require_auth();
+        if project.status != ProjectStatus::Funded {
             return Err(BoundlessError::InvalidOperation);
         }
+        let payout_key = DataKey::MilestonePayout(project_id.clone(), milestone_number);
+        let payout: MilestonePayout = env
+            .storage()
+            .persistent()
+            .get(&payout_key)
+            .ok_or(BoundlessError::NotFound)?;
+
+        let current_time = env.ledger().timestamp();
+        if current_time < payout.claimable_at {
+            return Err(BoundlessError::ChallengePeriodActive);
+        }
 
-        // Create a new milestone with updated status
-        let updated_milestone = Milestone {
-            status: MilestoneStatus::Approved,
-            ..milestone
-        };
+        // Part of every payout is retained in escrow until the project completes
+        let config = Self::get_config(env.clone());
+        let held_amount = pro_rata(
+            payout.approved_amount,
+            config.holdback_bps as u64,
+            BPS_DENOMINATOR as u64,
+        ) as u64;
+        let payable = payout.approved_amount - held_amount;
+        project.total_held_back += held_amount;
 
-        // Replace the milestone in the vector
-        project.milestones.set(milestone_number, updated_milestone);
+        let mut paid_amount = payable;
+        if config.vesting_duration == 0 {
+            release_to_creator(&env, &project, payable)?;
+            project.total_released += payable;
+        } else {
+            // The approved amount stays in escrow and vests to the creator over time
+            let streams_key = DataKey::VestingStreams(project_id.clone());
+            let mut streams: Vec<VestingStream> = env
+                .storage()
+                .persistent()
+                .get(&streams_key)
+                .unwrap_or(Vec::new(&env));
+            streams.push_back(VestingStream {
+                milestone_number,
+                amount: payable,
+                withdrawn: 0,
+                start: current_time,
+                duration: config.vesting_duration,
+            });
+            env.storage().persistent().set(&streams_key, &streams);
+            paid_amount = 0;
+        }
 
-        // Add to approvals
-        project
-            .milestone_approvals
-            .push_back((milestone_number, true));
+        let milestone = project.milestones.get_unchecked(milestone_number);
+        let mut rolled_over_to = None;
+        let mut returned_amount = 0;
+        if payout.remainder_amount > 0 {
+            match payout.remainder {
+                RemainderAction::RollOver => {
+                    if project.milestones.len() >= 100 {
+                        return Err(BoundlessError::InvalidMilestone);
+                    }
+                    let follow_up = project.milestones.len();
+                    project.milestones.push_back(Milestone {
+                        number: follow_up,
+                        description: milestone.description.clone(),
+                        amount: payout.remainder_amount,
+                        status: MilestoneStatus::Pending,
+                        released_at: None,
+                        completed_at: None,
+                        due_date: milestone.due_date.max(current_time)
+                            + MILESTONE_EXTENSION_SECONDS,
+                        paid_amount: 0,
+                        returned_amount: 0,
+                        held_amount: 0,
+                        rolled_over_to: None,
+                        evidence: Vec::new(&env),
+                        reviews: Vec::new(&env),
+                    });
+                    project.milestone_count = project.milestones.len();
+                    rolled_over_to = Some(follow_up);
+                }
+                RemainderAction::Refund => {
+                    // The remainder stays in escrow and each backer claims their
+                    // share, measured against the funding at this point
+                    project.total_returned += payout.remainder_amount;
+                    returned_amount = payout.remainder_amount;
+                    let contributions = env
+                        .storage()
+                        .persistent()
+                        .get::<_, Vec<BackerContribution>>(&DataKey::Backers(project_id.clone()))
+                        .map(|backers| backers.len())
+                        .unwrap_or(0);
+                    let returns_key = DataKey::ReturnedFunds(project_id.clone());
+                    let mut returns: Vec<ReturnedFunds> = env
+                        .storage()
+                        .persistent()
+                        .get(&returns_key)
+                        .unwrap_or(Vec::new(&env));
+                    returns.push_back(ReturnedFunds {
+                        milestone_number,
+                        amount: returned_amount,
+                        total_funded: project.total_funded,
+                        contributions,
+                    });
+                    env.storage().persistent().set(&returns_key, &returns);
+                    env.events().publish(
+                        ("project", "returned"),
+                        (project_id.clone(), milestone_number, returned_amount),
+                    );
+                }
+            }
+        }
 
+        project.milestones.set(
+            milestone_number,
+            Milestone {
+                paid_amount,
+                returned_amount,
+                held_amount,
+                rolled_over_to,
+                ..milestone
+            },
+        );
+        env.storage().persistent().remove(&payout_key);
+        env.events().publish(
+            (DataKey::Project(project_id.clone()), symbol_short!("paid")),
+            (milestone_number, payable),
+        );
+
+        complete_if_delivered(&env, &project_id, &mut project)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+        Ok(())
+    }
+    fn get_milestone_payout(
+        env: Env,
+        project_id: String,
+        milestone_number: u32,
+    ) -> Result<MilestonePayout, BoundlessError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MilestonePayout(project_id, milestone_number))
+            .ok_or(BoundlessError::NotFound)
+    }
+    fn withdraw_vested(env: Env, project_id: String) -> Result<u64, BoundlessError> {
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        project.creator.require_auth();
+        if project.status != ProjectStatus::Funded && project.status != ProjectStatus::Completed {
+            return Err(BoundlessError::InvalidOperation);
+        }
+
+        let withdrawn = withdraw_vested_streams(&env, &project_id, &mut project, false)?;
         env.storage()
             .persistent()
             .set(&DataKey::Project(project_id.clone()), &project);
         env.events().publish(
             (
                 DataKey::Project(project_id.clone()),
-                symbol_short!("approved"),
+                symbol_short!("vested"),
             ),
-            milestone_number,
+            withdrawn,
         );
-        Ok(())
+        Ok(withdrawn)
+    }
+    fn get_vesting_streams(
+        env: Env,
+        project_id: String,
+    ) -> Result<Vec<VestingStream>, BoundlessError> {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Project(project_id.clone()))
+        {
+            return Err(BoundlessError::NotFound);
+        }
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::VestingStreams(project_id))
+            .unwrap_or(Vec::new(&env)))
     }
     fn reject_milestone(
         env: Env,
         project_id: String,
         milestone_number: u32,
         admin: Address,
+        reason: ReviewReason,
+        feedback_uri: String,
+        feedback_hash: BytesN<32>,
     ) -> Result<(), BoundlessError> {
         admin.require_auth();
+        let admin_address: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(BoundlessError::NotFound)?;
+        if admin_address != admin {
+            return Err(BoundlessError::Unauthorized);
+        }
         let mut project: Project = env
             .storage()
             .persistent()
             .get(&DataKey::Project(project_id.clone()))
             .ok_or(BoundlessError::NotFound)?;
-        if project.status == ProjectStatus::Voting || project.status == ProjectStatus::Funding {
+        if project.status != ProjectStatus::Funded {
             return Err(BoundlessError::InvalidOperation);
         }
         if project.creator == admin {
@@ -137,21 +562,42 @@ impl MilestoneOperations for BoundlessContract {
         if project.milestones.len() <= milestone_number {
             return Err(BoundlessError::InvalidOperation);
         }
+        // A rejection has to tell the creator what to fix
+        if reason == ReviewReason::Delivered {
+            return Err(BoundlessError::InvalidOperation);
+        }
 
         let milestone = project.milestones.get_unchecked(milestone_number);
         if milestone.status != MilestoneStatus::Released {
             return Err(BoundlessError::InvalidOperation);
         }
 
+        let current_time = env.ledger().timestamp();
+        let mut reviews = milestone.reviews.clone();
+        reviews.push_back(MilestoneReview {
+            reviewer: admin.clone(),
+            approved: false,
+            reason,
+            feedback_uri: feedback_uri.clone(),
+            feedback_hash: feedback_hash.clone(),
+            reviewed_at: current_time,
+        });
+
         // Create a new milestone with updated status
         let updated_milestone = Milestone {
             status: MilestoneStatus::Rejected,
+            completed_at: Some(current_time),
+            reviews,
             ..milestone
         };
 
         // Replace the milestone in the vector
         project.milestones.set(milestone_number, updated_milestone);
 
+        project
+            .milestone_approvals
+            .push_back((milestone_number, false));
+
         env.storage()
             .persistent()
             .set(&DataKey::Project(project_id.clone()), &project);
@@ -160,10 +606,32 @@ impl MilestoneOperations for BoundlessContract {
                 DataKey::Project(project_id.clone()),
                 symbol_short!("rejected"),
             ),
-            milestone_number,
+            MilestoneRejectedEvent {
+                project_id,
+                milestone_number,
+                reviewer: admin,
+                reason,
+                feedback_uri,
+                feedback_hash,
+            },
         );
         Ok(())
     }
+    fn get_milestone_reviews(
+        env: Env,
+        project_id: String,
+        milestone_number: u32,
+    ) -> Result<Vec<MilestoneReview>, BoundlessError> {
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if project.milestones.len() <= milestone_number {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        Ok(project.milestones.get_unchecked(milestone_number).reviews)
+    }
     fn get_milestone_status(
         env: Env,
         project_id: String,
@@ -190,4 +658,823 @@ impl MilestoneOperations for BoundlessContract {
             .ok_or(BoundlessError::NotFound)?;
         Ok(project.milestones)
     }
+    fn get_overdue_milestones(env: Env, project_id: String) -> Result<Vec<u32>, BoundlessError> {
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+
+        let current_time = env.ledger().timestamp();
+        let mut overdue = Vec::new(&env);
+        let mut i = 0;
+        while i < project.milestones.len() {
+            let milestone = project.milestones.get_unchecked(i);
+            let undelivered = milestone.status == MilestoneStatus::Pending
+                || milestone.status == MilestoneStatus::Rejected;
+            if milestone.status == MilestoneStatus::Overdue
+                || (undelivered && milestone.due_date < current_time)
+            {
+                overdue.push_back(milestone.number);
+            }
+            i += 1;
+        }
+        Ok(overdue)
+    }
+    fn mark_overdue(
+        env: Env,
+        project_id: String,
+        milestone_number: u32,
+    ) -> Result<(), BoundlessError> {
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if project.status != ProjectStatus::Funded {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if project.milestones.len() <= milestone_number {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::OverdueVote(project_id.clone()))
+        {
+            return Err(BoundlessError::InvalidOperation);
+        }
+
+        let milestone = project.milestones.get_unchecked(milestone_number);
+        if milestone.status != MilestoneStatus::Pending
+            && milestone.status != MilestoneStatus::Rejected
+        {
+            return Err(BoundlessError::InvalidOperation);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if current_time <= milestone.due_date + MILESTONE_GRACE_PERIOD_SECONDS {
+            return Err(BoundlessError::MilestoneNotOverdue);
+        }
+
+        project.milestones.set(
+            milestone_number,
+            Milestone {
+                status: MilestoneStatus::Overdue,
+                ..milestone
+            },
+        );
+
+        let overdue_vote = OverdueVote {
+            milestone_number,
+            marked_at: current_time,
+            voting_deadline: current_time + OVERDUE_VOTING_PERIOD_SECONDS,
+            extend_weight: 0,
+            cancel_weight: 0,
+            voters: Vec::new(&env),
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::OverdueVote(project_id.clone()), &overdue_vote);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+        env.events().publish(
+            (
+                DataKey::Project(project_id.clone()),
+                symbol_short!("overdue"),
+            ),
+            milestone_number,
+        );
+        Ok(())
+    }
+    fn vote_overdue(
+        env: Env,
+        project_id: String,
+        backer: Address,
+        extend: bool,
+    ) -> Result<(), BoundlessError> {
+        backer.require_auth();
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        let mut overdue_vote: OverdueVote = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OverdueVote(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+
+        if overdue_vote.voting_deadline < env.ledger().timestamp() {
+            return Err(BoundlessError::VotingPeriodEnded);
+        }
+        if overdue_vote.voters.contains(&backer) {
+            return Err(BoundlessError::AlreadyVoted);
+        }
+
+        let weight = contribution_weight(&project, &backer);
+        if weight == 0 {
+            return Err(BoundlessError::NoBackerContributions);
+        }
+
+        if extend {
+            overdue_vote.extend_weight += weight;
+        } else {
+            overdue_vote.cancel_weight += weight;
+        }
+        overdue_vote.voters.push_back(backer);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::OverdueVote(project_id.clone()), &overdue_vote);
+        Ok(())
+    }
+    fn resolve_overdue(env: Env, project_id: String) -> Result<(), BoundlessError> {
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        let overdue_vote: OverdueVote = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OverdueVote(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+
+        // A project that was failed or cancelled meanwhile only drops the vote
+        if project.status != ProjectStatus::Funded {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::OverdueVote(project_id.clone()));
+            return Ok(());
+        }
+
+        // Voting can be cut short once either side holds a majority of the funds
+        let current_time = env.ledger().timestamp();
+        let majority = project.total_funded / 2;
+        if current_time <= overdue_vote.voting_deadline
+            && overdue_vote.extend_weight <= majority
+            && overdue_vote.cancel_weight <= majority
+        {
+            return Err(BoundlessError::InvalidOperation);
+        }
+
+        let milestone_number = overdue_vote.milestone_number;
+        let milestone = project.milestones.get_unchecked(milestone_number);
+
+        // Cancellation needs a strict majority of the cast weight, ties extend
+        if overdue_vote.cancel_weight > overdue_vote.extend_weight {
+            cancel_project(&env, &project_id, &mut project, ProjectStatus::Cancelled)?;
+        } else {
+            project.milestones.set(
+                milestone_number,
+                Milestone {
+                    status: MilestoneStatus::Pending,
+                    due_date: current_time + MILESTONE_EXTENSION_SECONDS,
+                    ..milestone
+                },
+            );
+            env.events().publish(
+                (
+                    DataKey::Project(project_id.clone()),
+                    symbol_short!("extended"),
+                ),
+                milestone_number,
+            );
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::OverdueVote(project_id.clone()));
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+        Ok(())
+    }
+    fn get_overdue_vote(env: Env, project_id: String) -> Result<OverdueVote, BoundlessError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OverdueVote(project_id))
+            .ok_or(BoundlessError::NotFound)
+    }
+    fn propose_milestone_amendment(
+        env: Env,
+        project_id: String,
+        creator: Address,
+        milestones: Vec<MilestonePlanItem>,
+    ) -> Result<(), BoundlessError> {
+        creator.require_auth();
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if project.is_closed {
+            return Err(BoundlessError::ProjectClosed);
+        }
+        if !is_milestone_plan_locked(&project)
+            || project.status == ProjectStatus::Failed
+            || project.status == ProjectStatus::Cancelled
+        {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::MilestoneAmendment(project_id.clone()))
+        {
+            return Err(BoundlessError::InvalidOperation);
+        }
+
+        // Only the pending tail of the plan can be amended
+        let first_milestone = first_amendable_milestone(&project);
+        let mut current_total = 0_u64;
+        let mut i = first_milestone;
+        while i < project.milestones.len() {
+            current_total += project.milestones.get_unchecked(i).amount;
+            i += 1;
+        }
+
+        if milestones.is_empty() || first_milestone + milestones.len() > 100 {
+            return Err(BoundlessError::InvalidMilestone);
+        }
+        let current_time = env.ledger().timestamp();
+        let mut proposed_total = 0_u64;
+        let mut i = 0;
+        while i < milestones.len() {
+            let item = milestones.get_unchecked(i);
+            if item.amount == 0 || item.due_date <= current_time {
+                return Err(BoundlessError::InvalidMilestone);
+            }
+            proposed_total += item.amount;
+            i += 1;
+        }
+        // Amendments move escrow between milestones, they never change its total
+        if proposed_total != current_total {
+            return Err(BoundlessError::InvalidMilestone);
+        }
+        if !approve_team_action(
+            &env,
+            &project_id,
+            &project,
+            &creator,
+            TeamAction::AmendMilestones(milestones.clone()),
+        )? {
+            return Ok(());
+        }
+
+        let amendment = MilestoneAmendment {
+            first_milestone,
+            plan_length: project.milestones.len(),
+            milestones,
+            proposed_at: current_time,
+            voting_deadline: current_time + AMENDMENT_VOTING_PERIOD_SECONDS,
+            approve_weight: 0,
+            reject_weight: 0,
+            voters: Vec::new(&env),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::MilestoneAmendment(project_id.clone()), &amendment);
+        env.events().publish(
+            (
+                DataKey::Project(project_id.clone()),
+                symbol_short!("amend_new"),
+            ),
+            first_milestone,
+        );
+        Ok(())
+    }
+    fn vote_milestone_amendment(
+        env: Env,
+        project_id: String,
+        backer: Address,
+        approve: bool,
+    ) -> Result<(), BoundlessError> {
+        backer.require_auth();
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        let mut amendment: MilestoneAmendment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MilestoneAmendment(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+
+        if amendment.voting_deadline < env.ledger().timestamp() {
+            return Err(BoundlessError::VotingPeriodEnded);
+        }
+        if amendment.voters.contains(&backer) {
+            return Err(BoundlessError::AlreadyVoted);
+        }
+
+        let weight = contribution_weight(&project, &backer);
+        if weight == 0 {
+            return Err(BoundlessError::NoBackerContributions);
+        }
+
+        if approve {
+            amendment.approve_weight += weight;
+        } else {
+            amendment.reject_weight += weight;
+        }
+        amendment.voters.push_back(backer);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::MilestoneAmendment(project_id.clone()), &amendment);
+        Ok(())
+    }
+    fn finalize_milestone_amendment(env: Env, project_id: String) -> Result<bool, BoundlessError> {
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        let amendment: MilestoneAmendment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MilestoneAmendment(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+
+        // Voting can be cut short once either side holds a majority of the funds
+        let majority = project.total_funded / 2;
+        if env.ledger().timestamp() <= amendment.voting_deadline
+            && amendment.approve_weight <= majority
+            && amendment.reject_weight <= majority
+        {
+            return Err(BoundlessError::InvalidOperation);
+        }
+
+        // The proposal is stale if a replaced milestone left `Pending` during the
+        // vote, or if stretch goals or roll-overs appended milestones meanwhile
+        let approved = amendment.approve_weight > amendment.reject_weight
+            && first_amendable_milestone(&project) <= amendment.first_milestone
+            && project.milestones.len() == amendment.plan_length;
+
+        if approved {
+            let mut milestones = project.milestones.slice(0..amendment.first_milestone);
+            let mut i = 0;
+            while i < amendment.milestones.len() {
+                let item = amendment.milestones.get_unchecked(i);
+                milestones.push_back(Milestone {
+                    number: milestones.len(),
+                    description: item.description,
+                    amount: item.amount,
+                    status: MilestoneStatus::Pending,
+                    released_at: None,
+                    completed_at: None,
+                    due_date: item.due_date,
+                    paid_amount: 0,
+                    returned_amount: 0,
+                    held_amount: 0,
+                    rolled_over_to: None,
+                    evidence: Vec::new(&env),
+                    reviews: Vec::new(&env),
+                });
+                i += 1;
+            }
+            project.milestone_count = milestones.len();
+            project.milestones = milestones;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Project(project_id.clone()), &project);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::MilestoneAmendment(project_id.clone()));
+        env.events().publish(
+            (
+                DataKey::Project(project_id.clone()),
+                if approved {
+                    symbol_short!("amended")
+                } else {
+                    symbol_short!("amend_rej")
+                },
+            ),
+            amendment.first_milestone,
+        );
+        Ok(approved)
+    }
+    fn get_milestone_amendment(
+        env: Env,
+        project_id: String,
+    ) -> Result<MilestoneAmendment, BoundlessError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MilestoneAmendment(project_id))
+            .ok_or(BoundlessError::NotFound)
+    }
+    fn add_stretch_goal(
+        env: Env,
+        project_id: String,
+        creator: Address,
+        threshold: u64,
+        description: String,
+        milestones: Vec<MilestonePlanItem>,
+    ) -> Result<u32, BoundlessError> {
+        creator.require_auth();
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if project.is_closed {
+            return Err(BoundlessError::ProjectClosed);
+        }
+        if is_milestone_plan_locked(&project) {
+            return Err(BoundlessError::MilestonePlanLocked);
+        }
+        if project.funding_model == FundingModel::Donation {
+            return Err(BoundlessError::InvalidOperation);
+        }
+
+        let mut goals: Vec<StretchGoal> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::StretchGoals(project_id.clone()))
+            .unwrap_or(Vec::new(&env));
+        let mut previous_threshold = project.funding_target;
+        let mut planned_milestones = project.milestone_count;
+        let mut i = 0;
+        while i < goals.len() {
+            let goal = goals.get_unchecked(i);
+            previous_threshold = goal.threshold;
+            planned_milestones += goal.milestones.len();
+            i += 1;
+        }
+        // Goals unlock in order, each one above the last
+        if threshold <= previous_threshold {
+            return Err(BoundlessError::InvalidFundingTarget);
+        }
+        if milestones.is_empty() || planned_milestones + milestones.len() > 100 {
+            return Err(BoundlessError::InvalidMilestone);
+        }
+
+        let current_time = env.ledger().timestamp();
+        let mut total = 0_u64;
+        let mut i = 0;
+        while i < milestones.len() {
+            let item = milestones.get_unchecked(i);
+            if item.amount == 0 || item.due_date <= current_time {
+                return Err(BoundlessError::InvalidMilestone);
+            }
+            total += item.amount;
+            i += 1;
+        }
+        // The goal's milestones account for exactly the extra funding it needs
+        if total != threshold - previous_threshold {
+            return Err(BoundlessError::InvalidMilestone);
+        }
+        // Until the team approves, this is the index the goal will take
+        if !approve_team_action(
+            &env,
+            &project_id,
+            &project,
+            &creator,
+            TeamAction::AddStretchGoal(threshold, description.clone(), milestones.clone()),
+        )? {
+            return Ok(goals.len());
+        }
+
+        goals.push_back(StretchGoal {
+            threshold,
+            description,
+            milestones,
+            reached_at: None,
+        });
+        env.storage()
+            .persistent()
+            .set(&DataKey::StretchGoals(project_id.clone()), &goals);
+        Ok(goals.len() - 1)
+    }
+    fn get_stretch_goals(env: Env, project_id: String) -> Result<Vec<StretchGoal>, BoundlessError> {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Project(project_id.clone()))
+        {
+            return Err(BoundlessError::NotFound);
+        }
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::StretchGoals(project_id))
+            .unwrap_or(Vec::new(&env)))
+    }
+}
+
+/// Approves `approved_bps` of a released milestone. The approved portion is
+/// claimable by the creator once the challenge window closes, at which point
+/// the rest is rolled over or refunded.
+fn approve(
+    env: &Env,
+    project_id: String,
+    milestone_number: u32,
+    approved_bps: u32,
+    remainder: RemainderAction,
+    review: MilestoneReview,
+) -> Result<(), BoundlessError> {
+    let admin_address: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Admin)
+        .ok_or(BoundlessError::NotFound)?;
+    if admin_address != review.reviewer {
+        return Err(BoundlessError::Unauthorized);
+    }
+    let mut project: Project = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Project(project_id.clone()))
+        .ok_or(BoundlessError::NotFound)?;
+    if project.status != ProjectStatus::Funded {
+        return Err(BoundlessError::InvalidOperation);
+    }
+    if project.creator == review.reviewer {
+        return Err(BoundlessError::InvalidOperation);
+    }
+    if project.milestones.len() <= milestone_number {
+        return Err(BoundlessError::InvalidOperation);
+    }
+    if approved_bps == 0 || approved_bps > BPS_DENOMINATOR {
+        return Err(BoundlessError::InvalidShare);
+    }
+
+    let milestone = project.milestones.get_unchecked(milestone_number);
+    if milestone.status != MilestoneStatus::Released {
+        return Err(BoundlessError::InvalidOperation);
+    }
+
+    // Keep-it-all projects that fell short of their target can only pay out
+    // the funded share of each milestone
+    let funded_amount = if project.funding_model == FundingModel::KeepItAll
+        && project.total_funded < project.funding_target
+    {
+        pro_rata(
+            milestone.amount,
+            project.total_funded,
+            project.funding_target,
+        ) as u64
+    } else {
+        milestone.amount
+    };
+    // The upfront tranche is paid out of the same escrow, so each milestone
+    // gives up its share of it
+    let upfront_bps = env
+        .storage()
+        .persistent()
+        .get::<_, UpfrontTranche>(&DataKey::UpfrontTranche(project_id.clone()))
+        .map(|tranche| tranche.bps)
+        .unwrap_or(0);
+    let funded_amount = pro_rata(
+        funded_amount,
+        (BPS_DENOMINATOR - upfront_bps) as u64,
+        BPS_DENOMINATOR as u64,
+    ) as u64;
+    let approved_amount =
+        pro_rata(funded_amount, approved_bps as u64, BPS_DENOMINATOR as u64) as u64;
+    let remainder_amount = funded_amount - approved_amount;
+
+    // Payout waits out the challenge window before the creator can claim it
+    let config = BoundlessContract::get_config(env.clone());
+    let payout = MilestonePayout {
+        milestone_number,
+        approved_amount,
+        remainder_amount,
+        remainder,
+        claimable_at: review.reviewed_at + config.challenge_period,
+        challenge_weight: 0,
+        challengers: Vec::new(env),
+    };
+    env.storage().persistent().set(
+        &DataKey::MilestonePayout(project_id.clone(), milestone_number),
+        &payout,
+    );
+
+    let mut reviews = milestone.reviews.clone();
+    reviews.push_back(review.clone());
+
+    // Create a new milestone with updated status
+    let updated_milestone = Milestone {
+        status: MilestoneStatus::Approved,
+        completed_at: Some(review.reviewed_at),
+        reviews,
+        ..milestone
+    };
+
+    // Replace the milestone in the vector
+    project.milestones.set(milestone_number, updated_milestone);
+
+    // Add to approvals
+    project
+        .milestone_approvals
+        .push_back((milestone_number, true));
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Project(project_id.clone()), &project);
+    env.events().publish(
+        (
+            DataKey::Project(project_id.clone()),
+            symbol_short!("approved"),
+        ),
+        MilestoneApprovedEvent {
+            project_id,
+            milestone_number,
+            approved_amount,
+            reviewer: review.reviewer,
+            reason: review.reason,
+            feedback_uri: review.feedback_uri,
+            feedback_hash: review.feedback_hash,
+        },
+    );
+    Ok(())
+}
+
+/// Pays the creator everything vested so far across the project's streams and
+/// drops finished streams. With `stop` set, streams are closed at their vested
+/// amount and the unvested rest stays in escrow.
+fn withdraw_vested_streams(
+    env: &Env,
+    project_id: &String,
+    project: &mut Project,
+    stop: bool,
+) -> Result<u64, BoundlessError> {
+    let streams_key = DataKey::VestingStreams(project_id.clone());
+    let streams: Vec<VestingStream> = env
+        .storage()
+        .persistent()
+        .get(&streams_key)
+        .unwrap_or(Vec::new(env));
+
+    let current_time = env.ledger().timestamp();
+    let mut remaining = Vec::new(env);
+    let mut total = 0_u64;
+    let mut i = 0;
+    while i < streams.len() {
+        let mut stream = streams.get_unchecked(i);
+        let elapsed = current_time
+            .saturating_sub(stream.start)
+            .min(stream.duration);
+        let vested = pro_rata(stream.amount, elapsed, stream.duration) as u64;
+        let due = vested - stream.withdrawn;
+        if due > 0 {
+            let milestone = project.milestones.get_unchecked(stream.milestone_number);
+            project.milestones.set(
+                stream.milestone_number,
+                Milestone {
+                    paid_amount: milestone.paid_amount + due,
+                    ..milestone
+                },
+            );
+            total += due;
+        }
+        stream.withdrawn = vested;
+        if !stop && stream.withdrawn < stream.amount {
+            remaining.push_back(stream);
+        }
+        i += 1;
+    }
+
+    release_to_creator(env, project, total)?;
+    project.total_released += total;
+    if remaining.is_empty() {
+        env.storage().persistent().remove(&streams_key);
+    } else {
+        env.storage().persistent().set(&streams_key, &remaining);
+    }
+    Ok(total)
+}
+
+/// Settles vesting when a project is cancelled: the creator keeps what has
+/// vested and the unvested remainder is clawed back into the refundable escrow.
+pub(crate) fn settle_vesting_streams(
+    env: &Env,
+    project_id: &String,
+    project: &mut Project,
+) -> Result<(), BoundlessError> {
+    withdraw_vested_streams(env, project_id, project, true)?;
+    Ok(())
+}
+
+/// Completes the project once every milestone is approved and paid out,
+/// releasing the held back retention to the creator.
+fn complete_if_delivered(
+    env: &Env,
+    project_id: &String,
+    project: &mut Project,
+) -> Result<(), BoundlessError> {
+    let mut i = 0;
+    while i < project.milestones.len() {
+        if project.milestones.get_unchecked(i).status != MilestoneStatus::Approved
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::MilestonePayout(project_id.clone(), i))
+        {
+            return Ok(());
+        }
+        i += 1;
+    }
+
+    release_to_creator(env, project, project.total_held_back)?;
+    project.total_released += project.total_held_back;
+    project.total_held_back = 0;
+    project.status = ProjectStatus::Completed;
+    return_bond(env, project_id, project)?;
+    env.events().publish(
+        (
+            DataKey::Project(project_id.clone()),
+            symbol_short!("completed"),
+        ),
+        project.total_released,
+    );
+    Ok(())
+}
+
+/// Index of the first milestone in the pending tail of the plan, i.e. after
+/// the last milestone that has been submitted, reviewed or marked overdue.
+fn first_amendable_milestone(project: &Project) -> u32 {
+    let mut first = project.milestones.len();
+    while first > 0
+        && project.milestones.get_unchecked(first - 1).status == MilestoneStatus::Pending
+    {
+        first -= 1;
+    }
+    first
+}
+
+/// Whether the project still has a stretch goal that funding has not reached.
+pub(crate) fn has_locked_stretch_goal(env: &Env, project_id: &String) -> bool {
+    let goals: Vec<StretchGoal> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::StretchGoals(project_id.clone()))
+        .unwrap_or(Vec::new(env));
+    match goals.last() {
+        Some(goal) => goal.reached_at.is_none(),
+        None => false,
+    }
+}
+
+/// Unlocks every stretch goal that the project's funding has reached,
+/// appending its milestones to the plan.
+pub(crate) fn unlock_stretch_goals(env: &Env, project_id: &String, project: &mut Project) {
+    let mut goals: Vec<StretchGoal> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::StretchGoals(project_id.clone()))
+        .unwrap_or(Vec::new(env));
+    let mut unlocked = false;
+    let mut i = 0;
+    while i < goals.len() {
+        let mut goal = goals.get_unchecked(i);
+        if goal.reached_at.is_none() && project.total_funded >= goal.threshold {
+            let mut j = 0;
+            while j < goal.milestones.len() {
+                let item = goal.milestones.get_unchecked(j);
+                project.milestones.push_back(Milestone {
+                    number: project.milestones.len(),
+                    description: item.description,
+                    amount: item.amount,
+                    status: MilestoneStatus::Pending,
+                    released_at: None,
+                    completed_at: None,
+                    due_date: item.due_date,
+                    paid_amount: 0,
+                    returned_amount: 0,
+                    held_amount: 0,
+                    rolled_over_to: None,
+                    evidence: Vec::new(env),
+                    reviews: Vec::new(env),
+                });
+                j += 1;
+            }
+            if project.milestones.len() > project.milestone_count {
+                project.milestone_count = project.milestones.len();
+            }
+            goal.reached_at = Some(env.ledger().timestamp());
+            goals.set(i, goal.clone());
+            unlocked = true;
+            env.events().publish(
+                (
+                    DataKey::Project(project_id.clone()),
+                    symbol_short!("stretch"),
+                ),
+                (i, goal.threshold),
+            );
+        }
+        i += 1;
+    }
+    if unlocked {
+        env.storage()
+            .persistent()
+            .set(&DataKey::StretchGoals(project_id.clone()), &goals);
+    }
 }
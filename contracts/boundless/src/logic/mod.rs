@@ -1,5 +1,7 @@
 pub mod admin;
+pub mod audit;
 pub mod funding;
 pub mod milestone;
 pub mod project;
+pub mod team;
 pub mod voting;
@@ -1,12 +1,23 @@
 use crate::{
     datatypes::{
-        BoundlessError, DataKey, Project, ProjectStatus, FUNDING_PERIOD_LEDGERS,
-        VOTING_PERIOD_LEDGERS,
+        BackerContribution, BondRequirement, BondStatus, BoundlessError, CreatorBond, DataKey,
+        FundingModel, MetadataProposal, MetadataVersion, MilestoneStatus, PayoutSplit,
+        PayoutSplitProposal, Project, ProjectStatus, ProjectUpdate, SlashTarget, TeamAction,
+        UpfrontTranche, AMENDMENT_VOTING_PERIOD_SECONDS, BPS_DENOMINATOR, FUNDING_PERIOD_LEDGERS,
+        MAX_PAGE_SIZE, MAX_PAYOUT_RECIPIENTS, VOTING_PERIOD_LEDGERS,
+    },
+    interface::{ContractManagement, ProjectManagement},
+    logic::{
+        funding::unsettled_pledges,
+        milestone::settle_vesting_streams,
+        team::{approve_team_action, replace_team_member},
+        voting::contribution_weight,
     },
-    interface::ProjectManagement,
     BoundlessContract, BoundlessContractArgs, BoundlessContractClient,
 };
-use soroban_sdk::{contractimpl, symbol_short, Address, Env, String, Vec};
+use soroban_sdk::{
+    contractimpl, symbol_short, token::TokenClient, vec, Address, BytesN, Env, String, Vec,
+};
 
 #[contractimpl]
 impl ProjectManagement for BoundlessContract {
@@ -15,8 +26,10 @@ impl ProjectManagement for BoundlessContract {
         project_id: String,
         creator: Address,
         metadata_uri: String,
+        metadata_hash: BytesN<32>,
         funding_target: u64,
         milestone_count: u32,
+        funding_model: FundingModel,
     ) -> Result<(), BoundlessError> {
         if env
             .storage()
@@ -28,19 +41,49 @@ impl ProjectManagement for BoundlessContract {
         if funding_target == 0 {
             return Err(BoundlessError::InvalidFundingTarget);
         }
-        if milestone_count <= 4 || milestone_count > 100 {
+        // Donation projects have no milestones
+        let milestone_count = if funding_model == FundingModel::Donation {
+            0
+        } else if milestone_count <= 4 || milestone_count > 100 {
             return Err(BoundlessError::InvalidMilestone);
-        }
+        } else {
+            milestone_count
+        };
         creator.require_auth();
 
+        let requirement: Option<BondRequirement> =
+            env.storage().persistent().get(&DataKey::BondRequirement);
+        if let Some(requirement) = requirement {
+            if requirement.amount > 0 {
+                TokenClient::new(&env, &requirement.token)
+                    .try_transfer(
+                        &creator,
+                        &env.current_contract_address(),
+                        &requirement.amount,
+                    )
+                    .map_err(|_| BoundlessError::TransferFailed)?
+                    .map_err(|_| BoundlessError::TransferFailed)?;
+                env.storage().persistent().set(
+                    &DataKey::CreatorBond(project_id.clone()),
+                    &CreatorBond {
+                        token: requirement.token,
+                        amount: requirement.amount,
+                        status: BondStatus::Held,
+                        slash_target: requirement.slash_target,
+                        treasury: requirement.treasury,
+                    },
+                );
+            }
+        }
+
         let current_time = env.ledger().timestamp();
         let funding_deadline = current_time + FUNDING_PERIOD_LEDGERS as u64; // 14 days
         let voting_deadline = current_time + VOTING_PERIOD_LEDGERS as u64; // 14 days
 
-        let project = Project {
+        let mut project = Project {
             project_id: project_id.clone(),
             creator,
-            metadata_uri,
+            metadata_uri: metadata_uri.clone(),
             funding_target,
             milestone_count,
             current_milestone: 0,
@@ -58,11 +101,24 @@ impl ProjectManagement for BoundlessContract {
             funding_deadline,
             milestones: Vec::new(&env),
             status: ProjectStatus::Voting,
+            total_released: 0,
+            total_returned: 0,
+            total_held_back: 0,
+            total_attested: 0,
+            attested_backers: Vec::new(&env),
+            funding_model,
         };
+        // The initial metadata is version 1 of the history
+        apply_metadata_update(&env, &project_id, &mut project, metadata_uri, metadata_hash);
 
         env.storage()
             .persistent()
             .set(&DataKey::Project(project_id.clone()), &project);
+        let mut projects = Self::list_projects(env.clone())?;
+        projects.push_back(project_id.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Projects, &projects);
         env.events().publish(
             (
                 DataKey::Project(project_id.clone()),
@@ -83,6 +139,7 @@ impl ProjectManagement for BoundlessContract {
         project_id: String,
         creator: Address,
         new_metadata_uri: String,
+        metadata_hash: BytesN<32>,
     ) -> Result<(), BoundlessError> {
         creator.require_auth();
         let mut project: Project = env
@@ -90,14 +147,256 @@ impl ProjectManagement for BoundlessContract {
             .persistent()
             .get(&DataKey::Project(project_id.clone()))
             .ok_or(BoundlessError::NotFound)?;
+        if !approve_team_action(
+            &env,
+            &project_id,
+            &project,
+            &creator,
+            TeamAction::UpdateMetadata(new_metadata_uri.clone(), metadata_hash.clone()),
+        )? {
+            return Ok(());
+        }
+
+        // Backers fund what the metadata promised, so once someone has funded
+        // or pledged to the project a change needs their approval
+        if !funding_started(&env, &project_id, &project) {
+            apply_metadata_update(
+                &env,
+                &project_id,
+                &mut project,
+                new_metadata_uri,
+                metadata_hash,
+            );
+            env.storage()
+                .persistent()
+                .set(&DataKey::Project(project_id.clone()), &project);
+            return Ok(());
+        }
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::MetadataProposal(project_id.clone()))
+        {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        let current_time = env.ledger().timestamp();
+        let proposal = MetadataProposal {
+            uri: new_metadata_uri,
+            hash: metadata_hash,
+            proposed_at: current_time,
+            voting_deadline: current_time + AMENDMENT_VOTING_PERIOD_SECONDS,
+            approve_weight: 0,
+            reject_weight: 0,
+            voters: Vec::new(&env),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::MetadataProposal(project_id.clone()), &proposal);
+        env.events().publish(
+            (
+                DataKey::Project(project_id.clone()),
+                symbol_short!("meta_new"),
+            ),
+            (proposal.uri, proposal.hash),
+        );
+        Ok(())
+    }
+    fn vote_metadata_update(
+        env: Env,
+        project_id: String,
+        backer: Address,
+        approve: bool,
+    ) -> Result<(), BoundlessError> {
+        backer.require_auth();
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        let mut proposal: MetadataProposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MetadataProposal(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+
+        if proposal.voting_deadline < env.ledger().timestamp() {
+            return Err(BoundlessError::VotingPeriodEnded);
+        }
+        if proposal.voters.contains(&backer) {
+            return Err(BoundlessError::AlreadyVoted);
+        }
+
+        let weight = contribution_weight(&project, &backer)
+            + unsettled_pledges(&env, &project_id, Some(&backer));
+        if weight == 0 {
+            return Err(BoundlessError::NoBackerContributions);
+        }
+
+        if approve {
+            proposal.approve_weight += weight;
+        } else {
+            proposal.reject_weight += weight;
+        }
+        proposal.voters.push_back(backer);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::MetadataProposal(project_id.clone()), &proposal);
+        Ok(())
+    }
+    fn finalize_metadata_update(env: Env, project_id: String) -> Result<bool, BoundlessError> {
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        let proposal: MetadataProposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MetadataProposal(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+
+        // Voting can be cut short once either side holds a majority of the funds
+        let majority = (project.total_funded + unsettled_pledges(&env, &project_id, None)) / 2;
+        if env.ledger().timestamp() <= proposal.voting_deadline
+            && proposal.approve_weight <= majority
+            && proposal.reject_weight <= majority
+        {
+            return Err(BoundlessError::InvalidOperation);
+        }
+
+        let approved = proposal.approve_weight > proposal.reject_weight;
+        env.storage()
+            .persistent()
+            .remove(&DataKey::MetadataProposal(project_id.clone()));
+        if approved {
+            apply_metadata_update(&env, &project_id, &mut project, proposal.uri, proposal.hash);
+            env.storage()
+                .persistent()
+                .set(&DataKey::Project(project_id.clone()), &project);
+        } else {
+            env.events().publish(
+                (
+                    DataKey::Project(project_id.clone()),
+                    symbol_short!("meta_rej"),
+                ),
+                (proposal.uri, proposal.hash),
+            );
+        }
+        Ok(approved)
+    }
+    fn get_metadata_proposal(
+        env: Env,
+        project_id: String,
+    ) -> Result<MetadataProposal, BoundlessError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MetadataProposal(project_id))
+            .ok_or(BoundlessError::NotFound)
+    }
+    fn get_metadata_history(
+        env: Env,
+        project_id: String,
+    ) -> Result<Vec<MetadataVersion>, BoundlessError> {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Project(project_id.clone()))
+        {
+            return Err(BoundlessError::NotFound);
+        }
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::MetadataHistory(project_id))
+            .unwrap_or(Vec::new(&env)))
+    }
+    fn post_project_update(
+        env: Env,
+        project_id: String,
+        creator: Address,
+        uri: String,
+        hash: BytesN<32>,
+        milestone_number: Option<u32>,
+    ) -> Result<u32, BoundlessError> {
+        creator.require_auth();
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
         if project.creator != creator {
             return Err(BoundlessError::Unauthorized);
         }
-        project.metadata_uri = new_metadata_uri;
+        if let Some(milestone_number) = milestone_number {
+            if milestone_number >= project.milestones.len() {
+                return Err(BoundlessError::InvalidMilestone);
+            }
+        }
+
+        let index: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProjectUpdateCount(project_id.clone()))
+            .unwrap_or(0);
+        let update = ProjectUpdate {
+            index,
+            uri,
+            hash,
+            milestone_number,
+            posted_at: env.ledger().timestamp(),
+        };
         env.storage()
             .persistent()
-            .set(&DataKey::Project(project_id.clone()), &project);
-        Ok(())
+            .set(&DataKey::ProjectUpdate(project_id.clone(), index), &update);
+        env.storage().persistent().set(
+            &DataKey::ProjectUpdateCount(project_id.clone()),
+            &(index + 1),
+        );
+        env.events().publish(
+            (
+                DataKey::Project(project_id.clone()),
+                symbol_short!("update"),
+            ),
+            update,
+        );
+        Ok(index)
+    }
+    fn get_project_updates(
+        env: Env,
+        project_id: String,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<ProjectUpdate>, BoundlessError> {
+        let count = Self::get_project_update_count(env.clone(), project_id.clone())?;
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+        let mut updates = Vec::new(&env);
+        let mut index = start;
+        while index < end {
+            updates.push_back(
+                env.storage()
+                    .persistent()
+                    .get(&DataKey::ProjectUpdate(project_id.clone(), index))
+                    .ok_or(BoundlessError::NotFound)?,
+            );
+            index += 1;
+        }
+        Ok(updates)
+    }
+    fn get_project_update_count(env: Env, project_id: String) -> Result<u32, BoundlessError> {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Project(project_id.clone()))
+        {
+            return Err(BoundlessError::NotFound);
+        }
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProjectUpdateCount(project_id))
+            .unwrap_or(0))
     }
     fn update_project_milestone_count(
         env: Env,
@@ -111,8 +410,23 @@ impl ProjectManagement for BoundlessContract {
             .persistent()
             .get(&DataKey::Project(project_id.clone()))
             .ok_or(BoundlessError::NotFound)?;
-        if project.creator != creator {
-            return Err(BoundlessError::Unauthorized);
+        if is_milestone_plan_locked(&project) {
+            return Err(BoundlessError::MilestonePlanLocked);
+        }
+        if new_milestone_count <= 4
+            || new_milestone_count > 100
+            || new_milestone_count < project.milestones.len()
+        {
+            return Err(BoundlessError::InvalidMilestone);
+        }
+        if !approve_team_action(
+            &env,
+            &project_id,
+            &project,
+            &creator,
+            TeamAction::SetMilestoneCount(new_milestone_count),
+        )? {
+            return Ok(());
         }
         project.milestone_count = new_milestone_count;
         env.storage()
@@ -132,8 +446,23 @@ impl ProjectManagement for BoundlessContract {
             .persistent()
             .get(&DataKey::Project(project_id.clone()))
             .ok_or(BoundlessError::NotFound)?;
-        if project.creator != caller {
-            return Err(BoundlessError::Unauthorized);
+        if is_milestone_plan_locked(&project) {
+            return Err(BoundlessError::MilestonePlanLocked);
+        }
+        if new_milestone_count <= 4
+            || new_milestone_count > 100
+            || new_milestone_count < project.milestones.len()
+        {
+            return Err(BoundlessError::InvalidMilestone);
+        }
+        if !approve_team_action(
+            &env,
+            &project_id,
+            &project,
+            &caller,
+            TeamAction::SetMilestoneCount(new_milestone_count),
+        )? {
+            return Ok(());
         }
         project.milestone_count = new_milestone_count;
         env.storage()
@@ -141,25 +470,429 @@ impl ProjectManagement for BoundlessContract {
             .set(&DataKey::Project(project_id.clone()), &project);
         Ok(())
     }
-    fn close_project(env: Env, project_id: String, creator: Address) -> Result<(), BoundlessError> {
+    fn set_upfront_bps(
+        env: Env,
+        project_id: String,
+        creator: Address,
+        upfront_bps: u32,
+    ) -> Result<(), BoundlessError> {
+        creator.require_auth();
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if is_milestone_plan_locked(&project) {
+            return Err(BoundlessError::MilestonePlanLocked);
+        }
+        if upfront_bps > Self::get_config(env.clone()).max_upfront_bps {
+            return Err(BoundlessError::InvalidShare);
+        }
+        if !approve_team_action(
+            &env,
+            &project_id,
+            &project,
+            &creator,
+            TeamAction::SetUpfrontBps(upfront_bps),
+        )? {
+            return Ok(());
+        }
+        env.storage().persistent().set(
+            &DataKey::UpfrontTranche(project_id),
+            &UpfrontTranche {
+                bps: upfront_bps,
+                claimed_amount: 0,
+                claimed_at: None,
+            },
+        );
+        Ok(())
+    }
+    fn set_payout_splits(
+        env: Env,
+        project_id: String,
+        creator: Address,
+        splits: Vec<PayoutSplit>,
+    ) -> Result<(), BoundlessError> {
+        creator.require_auth();
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if project.is_closed {
+            return Err(BoundlessError::ProjectClosed);
+        }
+        if splits.is_empty() || splits.len() > MAX_PAYOUT_RECIPIENTS {
+            return Err(BoundlessError::InvalidShare);
+        }
+        let mut total_bps = 0;
+        let mut i = 0;
+        while i < splits.len() {
+            let split = splits.get_unchecked(i);
+            if split.bps == 0 {
+                return Err(BoundlessError::InvalidShare);
+            }
+            total_bps += split.bps;
+            i += 1;
+        }
+        if total_bps != BPS_DENOMINATOR {
+            return Err(BoundlessError::InvalidShare);
+        }
+        if !approve_team_action(
+            &env,
+            &project_id,
+            &project,
+            &creator,
+            TeamAction::SetPayoutSplits(splits.clone()),
+        )? {
+            return Ok(());
+        }
+
+        // Until someone has funded or pledged to the project, the creator is
+        // free to change the split. From then on backers have to approve the change.
+        if !funding_started(&env, &project_id, &project) {
+            env.storage()
+                .persistent()
+                .set(&DataKey::PayoutSplits(project_id.clone()), &splits);
+            env.events().publish(
+                (
+                    DataKey::Project(project_id.clone()),
+                    symbol_short!("split_set"),
+                ),
+                splits,
+            );
+            return Ok(());
+        }
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::PayoutSplitProposal(project_id.clone()))
+        {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        let current_time = env.ledger().timestamp();
+        let proposal = PayoutSplitProposal {
+            splits,
+            proposed_at: current_time,
+            voting_deadline: current_time + AMENDMENT_VOTING_PERIOD_SECONDS,
+            approve_weight: 0,
+            reject_weight: 0,
+            voters: Vec::new(&env),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::PayoutSplitProposal(project_id.clone()), &proposal);
+        env.events().publish(
+            (
+                DataKey::Project(project_id.clone()),
+                symbol_short!("split_new"),
+            ),
+            proposal.splits,
+        );
+        Ok(())
+    }
+    fn vote_payout_splits(
+        env: Env,
+        project_id: String,
+        backer: Address,
+        approve: bool,
+    ) -> Result<(), BoundlessError> {
+        backer.require_auth();
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        let mut proposal: PayoutSplitProposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PayoutSplitProposal(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+
+        if proposal.voting_deadline < env.ledger().timestamp() {
+            return Err(BoundlessError::VotingPeriodEnded);
+        }
+        if proposal.voters.contains(&backer) {
+            return Err(BoundlessError::AlreadyVoted);
+        }
+
+        let weight = contribution_weight(&project, &backer)
+            + unsettled_pledges(&env, &project_id, Some(&backer));
+        if weight == 0 {
+            return Err(BoundlessError::NoBackerContributions);
+        }
+
+        if approve {
+            proposal.approve_weight += weight;
+        } else {
+            proposal.reject_weight += weight;
+        }
+        proposal.voters.push_back(backer);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::PayoutSplitProposal(project_id.clone()), &proposal);
+        Ok(())
+    }
+    fn finalize_payout_splits(env: Env, project_id: String) -> Result<bool, BoundlessError> {
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        let proposal: PayoutSplitProposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PayoutSplitProposal(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+
+        // Voting can be cut short once either side holds a majority of the funds
+        let majority = (project.total_funded + unsettled_pledges(&env, &project_id, None)) / 2;
+        if env.ledger().timestamp() <= proposal.voting_deadline
+            && proposal.approve_weight <= majority
+            && proposal.reject_weight <= majority
+        {
+            return Err(BoundlessError::InvalidOperation);
+        }
+
+        let approved = proposal.approve_weight > proposal.reject_weight;
+        if approved {
+            env.storage()
+                .persistent()
+                .set(&DataKey::PayoutSplits(project_id.clone()), &proposal.splits);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PayoutSplitProposal(project_id.clone()));
+        env.events().publish(
+            (
+                DataKey::Project(project_id.clone()),
+                if approved {
+                    symbol_short!("split_set")
+                } else {
+                    symbol_short!("split_rej")
+                },
+            ),
+            proposal.splits,
+        );
+        Ok(approved)
+    }
+    fn get_payout_splits(env: Env, project_id: String) -> Result<Vec<PayoutSplit>, BoundlessError> {
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        Ok(payout_splits(&env, &project))
+    }
+    fn get_payout_split_proposal(
+        env: Env,
+        project_id: String,
+    ) -> Result<PayoutSplitProposal, BoundlessError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PayoutSplitProposal(project_id))
+            .ok_or(BoundlessError::NotFound)
+    }
+    fn transfer_project_ownership(
+        env: Env,
+        project_id: String,
+        creator: Address,
+        new_owner: Address,
+    ) -> Result<(), BoundlessError> {
         creator.require_auth();
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if new_owner == project.creator {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if has_milestone_under_review(&project) {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if !approve_team_action(
+            &env,
+            &project_id,
+            &project,
+            &creator,
+            TeamAction::TransferOwnership(new_owner.clone()),
+        )? {
+            return Ok(());
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::PendingOwner(project_id.clone()), &new_owner);
+        env.events().publish(
+            (
+                DataKey::Project(project_id.clone()),
+                symbol_short!("owner_new"),
+            ),
+            new_owner,
+        );
+        Ok(())
+    }
+    fn accept_project_ownership(
+        env: Env,
+        project_id: String,
+        new_owner: Address,
+    ) -> Result<(), BoundlessError> {
+        new_owner.require_auth();
         let mut project: Project = env
             .storage()
             .persistent()
             .get(&DataKey::Project(project_id.clone()))
             .ok_or(BoundlessError::NotFound)?;
-        if project.creator != creator {
+        let pending_owner: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingOwner(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if pending_owner != new_owner {
             return Err(BoundlessError::Unauthorized);
         }
+        if has_milestone_under_review(&project) {
+            return Err(BoundlessError::InvalidOperation);
+        }
+
+        let previous_owner = project.creator.clone();
+        replace_team_member(&env, &project_id, &previous_owner, &new_owner);
+        project.creator = new_owner.clone();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingOwner(project_id.clone()));
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+        env.events().publish(
+            (DataKey::Project(project_id.clone()), symbol_short!("owner")),
+            (previous_owner, new_owner),
+        );
+        Ok(())
+    }
+    fn get_pending_owner(env: Env, project_id: String) -> Result<Address, BoundlessError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingOwner(project_id))
+            .ok_or(BoundlessError::NotFound)
+    }
+    fn close_project(env: Env, project_id: String, creator: Address) -> Result<(), BoundlessError> {
+        creator.require_auth();
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
         if project.status != ProjectStatus::Voting {
             return Err(BoundlessError::Unauthorized);
         }
+        if !approve_team_action(
+            &env,
+            &project_id,
+            &project,
+            &creator,
+            TeamAction::CloseProject,
+        )? {
+            return Ok(());
+        }
         project.is_closed = true;
         env.storage()
             .persistent()
             .set(&DataKey::Project(project_id.clone()), &project);
         Ok(())
     }
+    fn abandon_project(
+        env: Env,
+        project_id: String,
+        creator: Address,
+    ) -> Result<(), BoundlessError> {
+        creator.require_auth();
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if project.status != ProjectStatus::Funded {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if !approve_team_action(
+            &env,
+            &project_id,
+            &project,
+            &creator,
+            TeamAction::AbandonProject,
+        )? {
+            return Ok(());
+        }
+        cancel_project(&env, &project_id, &mut project, ProjectStatus::Cancelled)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+        Ok(())
+    }
+    fn force_fail_project(
+        env: Env,
+        admin: Address,
+        project_id: String,
+    ) -> Result<(), BoundlessError> {
+        admin.require_auth();
+        if Self::get_admin(&env) != admin {
+            return Err(BoundlessError::Unauthorized);
+        }
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if project.is_closed
+            || !matches!(
+                project.status,
+                ProjectStatus::Voting | ProjectStatus::Funding | ProjectStatus::Funded
+            )
+        {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        cancel_project(&env, &project_id, &mut project, ProjectStatus::Failed)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+        Ok(())
+    }
+    fn get_creator_bond(env: Env, project_id: String) -> Result<CreatorBond, BoundlessError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CreatorBond(project_id))
+            .ok_or(BoundlessError::NotFound)
+    }
+    fn reclaim_bond(env: Env, project_id: String) -> Result<(), BoundlessError> {
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        project.creator.require_auth();
+        // Bonds of projects that failed through moderation are slashed on the
+        // spot, so a held bond on a failed project means funding fell short.
+        // Donation projects have no milestones to complete, so theirs is
+        // returned once they are funded and the funding period is over
+        let donations_done = project.funding_model == FundingModel::Donation
+            && project.status == ProjectStatus::Funded
+            && env.ledger().timestamp() > project.funding_deadline;
+        if !project.is_closed && project.status != ProjectStatus::Failed && !donations_done {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        let bond: CreatorBond = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CreatorBond(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        if bond.status != BondStatus::Held {
+            return Err(BoundlessError::AlreadyClaimed);
+        }
+        return_bond(&env, &project_id, &project)
+    }
     fn get_project_status(env: Env, project_id: String) -> Result<ProjectStatus, BoundlessError> {
         let project: Project = env
             .storage()
@@ -189,3 +922,217 @@ impl ProjectManagement for BoundlessContract {
         ))
     }
 }
+
+/// The milestone plan is frozen once voting on the project has started; from
+/// then on it can only change through a backer-approved amendment.
+pub(crate) fn is_milestone_plan_locked(project: &Project) -> bool {
+    project.status != ProjectStatus::Voting || !project.votes.is_empty()
+}
+
+/// Points the project at new metadata and appends it to the version history.
+fn apply_metadata_update(
+    env: &Env,
+    project_id: &String,
+    project: &mut Project,
+    uri: String,
+    hash: BytesN<32>,
+) {
+    let mut history: Vec<MetadataVersion> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MetadataHistory(project_id.clone()))
+        .unwrap_or(Vec::new(env));
+    let version = MetadataVersion {
+        version: history.len() + 1,
+        uri: uri.clone(),
+        hash,
+        updated_at: env.ledger().timestamp(),
+    };
+    history.push_back(version.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::MetadataHistory(project_id.clone()), &history);
+    project.metadata_uri = uri;
+    env.events().publish(
+        (
+            DataKey::Project(project_id.clone()),
+            symbol_short!("metadata"),
+        ),
+        version,
+    );
+}
+
+/// Ownership cannot change hands while the admin is reviewing a submission.
+fn has_milestone_under_review(project: &Project) -> bool {
+    let mut i = 0;
+    while i < project.milestones.len() {
+        if project.milestones.get_unchecked(i).status == MilestoneStatus::Released {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Whether backers have committed to the project, through a contribution or
+/// a pledge that has yet to be settled.
+fn funding_started(env: &Env, project_id: &String, project: &Project) -> bool {
+    project.total_funded > 0
+        || env
+            .storage()
+            .persistent()
+            .has(&DataKey::Pledges(project_id.clone()))
+}
+
+/// Recipients of the project's payouts, the creator alone unless a split
+/// has been configured.
+pub(crate) fn payout_splits(env: &Env, project: &Project) -> Vec<PayoutSplit> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PayoutSplits(project.project_id.clone()))
+        .unwrap_or(vec![
+            env,
+            PayoutSplit {
+                recipient: project.creator.clone(),
+                bps: BPS_DENOMINATOR,
+            },
+        ])
+}
+
+/// Ends a project early through no fault of its backers: stops any vesting
+/// streams and slashes the creator bond.
+pub(crate) fn cancel_project(
+    env: &Env,
+    project_id: &String,
+    project: &mut Project,
+    status: ProjectStatus,
+) -> Result<(), BoundlessError> {
+    project.status = status;
+    settle_vesting_streams(env, project_id, project)?;
+    slash_bond(env, project_id, project)?;
+    env.events().publish(
+        (
+            DataKey::Project(project_id.clone()),
+            symbol_short!("cancelled"),
+        ),
+        project.status.clone(),
+    );
+    Ok(())
+}
+
+/// Returns a held creator bond to the creator, if there is one.
+pub(crate) fn return_bond(
+    env: &Env,
+    project_id: &String,
+    project: &Project,
+) -> Result<(), BoundlessError> {
+    let bond: Option<CreatorBond> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::CreatorBond(project_id.clone()));
+    let Some(mut bond) = bond else {
+        return Ok(());
+    };
+    if bond.status != BondStatus::Held {
+        return Ok(());
+    }
+    TokenClient::new(env, &bond.token)
+        .try_transfer(
+            &env.current_contract_address(),
+            &project.creator,
+            &bond.amount,
+        )
+        .map_err(|_| BoundlessError::TransferFailed)?
+        .map_err(|_| BoundlessError::TransferFailed)?;
+    bond.status = BondStatus::Returned;
+    env.storage()
+        .persistent()
+        .set(&DataKey::CreatorBond(project_id.clone()), &bond);
+    env.events().publish(
+        (
+            DataKey::Project(project_id.clone()),
+            symbol_short!("bond_back"),
+        ),
+        bond.amount,
+    );
+    Ok(())
+}
+
+/// Slashes a held creator bond, splitting it between backers pro rata to
+/// their contributions or sending it to the treasury. Projects without
+/// backers always slash to the treasury, as do shares that fail to transfer.
+pub(crate) fn slash_bond(
+    env: &Env,
+    project_id: &String,
+    project: &Project,
+) -> Result<(), BoundlessError> {
+    let bond: Option<CreatorBond> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::CreatorBond(project_id.clone()));
+    let Some(mut bond) = bond else {
+        return Ok(());
+    };
+    if bond.status != BondStatus::Held {
+        return Ok(());
+    }
+    let contract_address = env.current_contract_address();
+    let token_client = TokenClient::new(env, &bond.token);
+    let mut to_treasury = bond.amount;
+    if bond.slash_target == SlashTarget::Backers && project.total_funded > 0 {
+        let backer_contributions: Vec<BackerContribution> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Backers(project_id.clone()))
+            .unwrap_or(Vec::new(env));
+        let mut i = 0;
+        while i < backer_contributions.len() {
+            let backer_contribution = backer_contributions.get_unchecked(i);
+            let share =
+                bond.amount * backer_contribution.amount as i128 / project.total_funded as i128;
+            if share > 0 {
+                // A backer who can no longer receive the token must not block
+                // cancellation, so their share falls through to the treasury
+                let transferred = matches!(
+                    token_client.try_transfer(
+                        &contract_address,
+                        &backer_contribution.backer,
+                        &share
+                    ),
+                    Ok(Ok(()))
+                );
+                if transferred {
+                    to_treasury -= share;
+                } else {
+                    env.events().publish(
+                        (
+                            DataKey::Project(project_id.clone()),
+                            symbol_short!("bond_skip"),
+                        ),
+                        (backer_contribution.backer, share),
+                    );
+                }
+            }
+            i += 1;
+        }
+    }
+    // Rounding dust from the backer split also goes to the treasury
+    if to_treasury > 0 {
+        token_client
+            .try_transfer(&contract_address, &bond.treasury, &to_treasury)
+            .map_err(|_| BoundlessError::TransferFailed)?
+            .map_err(|_| BoundlessError::TransferFailed)?;
+    }
+    bond.status = BondStatus::Slashed;
+    env.storage()
+        .persistent()
+        .set(&DataKey::CreatorBond(project_id.clone()), &bond);
+    env.events().publish(
+        (
+            DataKey::Project(project_id.clone()),
+            symbol_short!("slashed"),
+        ),
+        bond.amount,
+    );
+    Ok(())
+}
@@ -0,0 +1,249 @@
+use crate::{
+    datatypes::{
+        BoundlessError, DataKey, Project, ProjectTeam, TeamAction, TeamApproval, MAX_TEAM_MEMBERS,
+    },
+    interface::TeamOperations,
+    BoundlessContract, BoundlessContractArgs, BoundlessContractClient,
+};
+use soroban_sdk::{contractimpl, symbol_short, vec, Address, Env, String, Vec};
+
+#[contractimpl]
+impl TeamOperations for BoundlessContract {
+    fn add_team_member(
+        env: Env,
+        project_id: String,
+        caller: Address,
+        member: Address,
+    ) -> Result<(), BoundlessError> {
+        caller.require_auth();
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        let mut team = project_team(&env, &project);
+        if team.members.contains(&member) {
+            return Err(BoundlessError::AlreadyExists);
+        }
+        if team.members.len() >= MAX_TEAM_MEMBERS {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if !approve_team_action(
+            &env,
+            &project_id,
+            &project,
+            &caller,
+            TeamAction::AddMember(member.clone()),
+        )? {
+            return Ok(());
+        }
+        team.members.push_back(member);
+        set_project_team(&env, &project_id, &team);
+        Ok(())
+    }
+    fn remove_team_member(
+        env: Env,
+        project_id: String,
+        caller: Address,
+        member: Address,
+    ) -> Result<(), BoundlessError> {
+        caller.require_auth();
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        let mut team = project_team(&env, &project);
+        let index = team
+            .members
+            .first_index_of(&member)
+            .ok_or(BoundlessError::NotFound)?;
+        // The team can never shrink below the approvals its actions need
+        if team.members.len() <= team.threshold {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if !approve_team_action(
+            &env,
+            &project_id,
+            &project,
+            &caller,
+            TeamAction::RemoveMember(member),
+        )? {
+            return Ok(());
+        }
+        team.members.remove(index);
+        set_project_team(&env, &project_id, &team);
+        Ok(())
+    }
+    fn set_team_threshold(
+        env: Env,
+        project_id: String,
+        caller: Address,
+        threshold: u32,
+    ) -> Result<(), BoundlessError> {
+        caller.require_auth();
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .ok_or(BoundlessError::NotFound)?;
+        let mut team = project_team(&env, &project);
+        if threshold == 0 || threshold > team.members.len() {
+            return Err(BoundlessError::InvalidOperation);
+        }
+        if !approve_team_action(
+            &env,
+            &project_id,
+            &project,
+            &caller,
+            TeamAction::SetThreshold(threshold),
+        )? {
+            return Ok(());
+        }
+        team.threshold = threshold;
+        set_project_team(&env, &project_id, &team);
+        Ok(())
+    }
+    fn get_project_team(env: Env, project_id: String) -> Result<ProjectTeam, BoundlessError> {
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(BoundlessError::NotFound)?;
+        Ok(project_team(&env, &project))
+    }
+    fn get_pending_team_actions(
+        env: Env,
+        project_id: String,
+    ) -> Result<Vec<TeamApproval>, BoundlessError> {
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::TeamApprovals(project_id))
+            .unwrap_or(Vec::new(&env)))
+    }
+}
+
+/// Members who jointly own the project, the creator alone unless a team has
+/// been formed.
+pub(crate) fn project_team(env: &Env, project: &Project) -> ProjectTeam {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ProjectTeam(project.project_id.clone()))
+        .unwrap_or(ProjectTeam {
+            members: vec![env, project.creator.clone()],
+            threshold: 1,
+        })
+}
+
+/// Records `caller`'s approval of `action` and returns whether the team has
+/// now approved it. Approvals are collected across transactions, one per
+/// member, and the action is cleared from the pending list once it passes.
+pub(crate) fn approve_team_action(
+    env: &Env,
+    project_id: &String,
+    project: &Project,
+    caller: &Address,
+    action: TeamAction,
+) -> Result<bool, BoundlessError> {
+    let team = project_team(env, project);
+    if !team.members.contains(caller) {
+        return Err(BoundlessError::Unauthorized);
+    }
+
+    let mut pending: Vec<TeamApproval> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::TeamApprovals(project_id.clone()))
+        .unwrap_or(Vec::new(env));
+    let mut index = pending.len();
+    let mut i = 0;
+    while i < pending.len() {
+        if pending.get_unchecked(i).action == action {
+            index = i;
+            break;
+        }
+        i += 1;
+    }
+    let mut approval = if index < pending.len() {
+        pending.get_unchecked(index)
+    } else {
+        TeamApproval {
+            action: action.clone(),
+            approvals: Vec::new(env),
+        }
+    };
+    if approval.approvals.contains(caller) {
+        return Err(BoundlessError::AlreadyVoted);
+    }
+    approval.approvals.push_back(caller.clone());
+
+    let approved = approval.approvals.len() >= team.threshold;
+    if approved {
+        if index < pending.len() {
+            pending.remove(index);
+        }
+    } else if index < pending.len() {
+        pending.set(index, approval);
+    } else {
+        pending.push_back(approval);
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::TeamApprovals(project_id.clone()), &pending);
+    if !approved {
+        env.events().publish(
+            (
+                DataKey::Project(project_id.clone()),
+                symbol_short!("team_ack"),
+            ),
+            action,
+        );
+    }
+    Ok(approved)
+}
+
+/// Swaps `previous` for `replacement` in a formed team, used when project
+/// ownership changes hands. Teams that were never formed follow the creator.
+pub(crate) fn replace_team_member(
+    env: &Env,
+    project_id: &String,
+    previous: &Address,
+    replacement: &Address,
+) {
+    let team: Option<ProjectTeam> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::ProjectTeam(project_id.clone()));
+    let Some(mut team) = team else {
+        return;
+    };
+    let Some(index) = team.members.first_index_of(previous) else {
+        return;
+    };
+    if team.members.contains(replacement) {
+        team.members.remove(index);
+        // Keep the threshold reachable after the two seats merge
+        if team.threshold > team.members.len() {
+            team.threshold = team.members.len();
+        }
+    } else {
+        team.members.set(index, replacement.clone());
+    }
+    set_project_team(env, project_id, &team);
+}
+
+/// Stores a changed team. Approvals collected under the old membership or
+/// threshold no longer apply, so pending actions are dropped.
+fn set_project_team(env: &Env, project_id: &String, team: &ProjectTeam) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::ProjectTeam(project_id.clone()), team);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::TeamApprovals(project_id.clone()));
+    env.events().publish(
+        (DataKey::Project(project_id.clone()), symbol_short!("team")),
+        team.clone(),
+    );
+}
@@ -155,3 +155,26 @@ impl VotingOperations for BoundlessContract {
         Err(BoundlessError::AlreadyVoted)
     }
 }
+
+/// Total amount a backer has contributed to a project across all tokens,
+/// used as their weight in backer votes.
+pub(crate) fn contribution_weight(project: &Project, backer: &Address) -> u64 {
+    let mut weight = 0_u64;
+    let mut i = 0;
+    while i < project.backers.len() {
+        let (address, amount, _) = project.backers.get_unchecked(i);
+        if address == *backer {
+            weight += amount;
+        }
+        i += 1;
+    }
+    let mut i = 0;
+    while i < project.attested_backers.len() {
+        let (address, amount) = project.attested_backers.get_unchecked(i);
+        if address == *backer {
+            weight += amount;
+        }
+        i += 1;
+    }
+    weight
+}
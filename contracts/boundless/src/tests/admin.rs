@@ -1,6 +1,9 @@
 #![cfg(test)]
 
-use crate::{datatypes::BoundlessError, BoundlessContract, BoundlessContractClient};
+use crate::{
+    datatypes::{BoundlessError, PlatformConfig},
+    BoundlessContract, BoundlessContractClient,
+};
 use soroban_sdk::{
     testutils::{Address as _, MockAuth, MockAuthInvoke},
     Address, BytesN, Env,
@@ -41,6 +44,72 @@ fn test_initialize_already_initialized() {
     let _err = contract.initialize(&admin);
 }
 
+#[test]
+fn test_set_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(BoundlessContract, ());
+
+    let contract = BoundlessContractClient::new(&env, &contract_id);
+    contract.initialize(&admin);
+
+    // Periods are in seconds, as they are compared against the ledger timestamp
+    assert_eq!(contract.get_config().challenge_period, 3 * 86_400);
+
+    let config = PlatformConfig {
+        challenge_period: 3600,
+        challenge_threshold_bps: 2500,
+        vesting_duration: 86400,
+        max_upfront_bps: 1500,
+        holdback_bps: 500,
+        donation_fee_bps: 250,
+    };
+    contract.set_config(&admin, &config);
+    assert_eq!(contract.get_config(), config);
+}
+
+#[test]
+fn test_set_config_share_above_full() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(BoundlessContract, ());
+
+    let contract = BoundlessContractClient::new(&env, &contract_id);
+    contract.initialize(&admin);
+
+    let mut config = contract.get_config();
+    config.max_upfront_bps = 10001;
+    let result = contract.try_set_config(&admin, &config);
+    assert_eq!(result, Err(Ok(BoundlessError::InvalidShare)));
+
+    let mut config = contract.get_config();
+    config.holdback_bps = 20000;
+    let result = contract.try_set_config(&admin, &config);
+    assert_eq!(result, Err(Ok(BoundlessError::InvalidShare)));
+}
+
+#[test]
+fn test_set_config_not_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(BoundlessContract, ());
+
+    let contract = BoundlessContractClient::new(&env, &contract_id);
+    contract.initialize(&admin);
+
+    let mut config = contract.get_config();
+    config.challenge_period = 0;
+    let not_admin = Address::generate(&env);
+    let result = contract.try_set_config(&not_admin, &config);
+    assert_eq!(result, Err(Ok(BoundlessError::Unauthorized)));
+}
+
 // #[test]
 // fn test_upgrade() {
 //     let env = Env::default();
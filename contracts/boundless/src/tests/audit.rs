@@ -0,0 +1,172 @@
+#![cfg(test)]
+
+use crate::{
+    datatypes::{AuditCheck, DataKey, FundingModel, Project, ProjectStatus, UpfrontTranche},
+    BoundlessContract, BoundlessContractClient,
+};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, BytesN, Env, String,
+};
+
+fn setup_funded_project<'a>(
+    env: &Env,
+    project_id: &String,
+) -> (BoundlessContractClient<'a>, TokenClient<'a>) {
+    env.mock_all_auths();
+    let admin = Address::generate(env);
+    let contract_id = env.register(BoundlessContract, ());
+    let client = BoundlessContractClient::new(env, &contract_id);
+    client.initialize(&admin);
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let creator = Address::generate(env);
+    client.create_project(
+        project_id,
+        &creator,
+        &String::from_str(env, "ipfs://test"),
+        &BytesN::from_array(env, &[0; 32]),
+        &500000,
+        &5,
+        &FundingModel::AllOrNothing,
+    );
+    update_project(env, &contract_id, project_id, |project| {
+        project.status = ProjectStatus::Funding
+    });
+    client.whitelist_token_contract(&admin, project_id, &token_id);
+
+    let backer = Address::generate(env);
+    StellarAssetClient::new(env, &token_id).mint(&backer, &200000);
+    client.fund_project(project_id, &200000, &backer, &token_id, &None);
+
+    (client, TokenClient::new(env, &token_id))
+}
+
+fn update_project(env: &Env, contract_id: &Address, project_id: &String, f: impl Fn(&mut Project)) {
+    env.as_contract(contract_id, || {
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .unwrap();
+        f(&mut project);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+    });
+}
+
+#[test]
+fn test_audit_consistent_project() {
+    let env = Env::default();
+    let project_id = String::from_str(&env, "test-project");
+    let (client, _) = setup_funded_project(&env, &project_id);
+
+    let report = client.audit_project(&project_id);
+    assert_eq!(report.total_funded, 200000);
+    assert_eq!(report.discrepancies.len(), 0);
+}
+
+#[test]
+fn test_audit_detects_drift() {
+    let env = Env::default();
+    let project_id = String::from_str(&env, "test-project");
+    let (client, token) = setup_funded_project(&env, &project_id);
+
+    // A total that no longer matches the recorded contributions, and escrow that left the contract
+    update_project(&env, &client.address, &project_id, |project| {
+        project.total_funded = 150000
+    });
+    token.burn(&client.address, &100000);
+
+    let report = client.audit_project(&project_id);
+    assert_eq!(report.discrepancies.len(), 3);
+    let records = report.discrepancies.get_unchecked(0);
+    assert_eq!(records.check, AuditCheck::BackerRecords);
+    assert_eq!((records.expected, records.actual), (150000, 200000));
+    assert_eq!(
+        report.discrepancies.get_unchecked(1).check,
+        AuditCheck::BackerList
+    );
+    let balance = report.discrepancies.get_unchecked(2);
+    assert_eq!(balance.check, AuditCheck::TokenBalance);
+    assert_eq!(balance.token, Some(token.address.clone()));
+    assert_eq!((balance.expected, balance.actual), (100000, 200000));
+}
+
+#[test]
+fn test_audit_consistent_after_outflows() {
+    let env = Env::default();
+    let project_id = String::from_str(&env, "test-project");
+    let (client, token) = setup_funded_project(&env, &project_id);
+
+    // An upfront release followed by refunds of the rest
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(
+            &DataKey::UpfrontTranche(project_id.clone()),
+            &UpfrontTranche {
+                bps: 1000,
+                claimed_amount: 0,
+                claimed_at: None,
+            },
+        );
+    });
+    update_project(&env, &client.address, &project_id, |project| {
+        project.status = ProjectStatus::Funded
+    });
+    client.claim_upfront(&project_id);
+    update_project(&env, &client.address, &project_id, |project| {
+        project.status = ProjectStatus::Failed
+    });
+    client.refund(&project_id, &token.address);
+    assert_eq!(client.get_escrow_balance(&project_id, &token.address), 0);
+
+    let report = client.audit_project(&project_id);
+    assert_eq!(report.discrepancies.len(), 0);
+}
+
+#[test]
+fn test_audit_detects_under_escrow() {
+    let env = Env::default();
+    let project_id = String::from_str(&env, "test-project");
+    let (client, token) = setup_funded_project(&env, &project_id);
+
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(
+            &DataKey::Escrow(project_id.clone(), token.address.clone()),
+            &150000_u64,
+        );
+    });
+
+    let report = client.audit_project(&project_id);
+    assert_eq!(report.discrepancies.len(), 1);
+    let escrow = report.discrepancies.get_unchecked(0);
+    assert_eq!(escrow.check, AuditCheck::EscrowLedger);
+    assert_eq!(escrow.token, Some(token.address.clone()));
+    assert_eq!((escrow.expected, escrow.actual), (200000, 150000));
+}
+
+#[test]
+fn test_audit_projects_paginated() {
+    let env = Env::default();
+    let project_id = String::from_str(&env, "test-project");
+    let (client, _) = setup_funded_project(&env, &project_id);
+    client.create_project(
+        &String::from_str(&env, "second-project"),
+        &Address::generate(&env),
+        &String::from_str(&env, "ipfs://second"),
+        &BytesN::from_array(&env, &[0; 32]),
+        &500000,
+        &5,
+        &FundingModel::AllOrNothing,
+    );
+
+    let reports = client.audit_projects(&0, &10);
+    assert_eq!(reports.len(), 2);
+    assert_eq!(reports.get_unchecked(0).project_id, project_id);
+    assert_eq!(client.audit_projects(&1, &10).len(), 1);
+    assert_eq!(client.audit_projects(&2, &10).len(), 0);
+}
@@ -2,15 +2,17 @@
 
 use crate::{
     datatypes::{
-        BackerContribution, BoundlessError, DataKey, Project, ProjectFundedEvent, ProjectStatus,
-        FUNDING_PERIOD_LEDGERS,
+        BackerContribution, BoundlessError, DataKey, FundingModel, MilestonePlanItem, PayoutSplit,
+        Project, ProjectFundedEvent, ProjectStatus, TierPrice, FUNDING_PERIOD_LEDGERS,
     },
+    logic::voting::contribution_weight,
     BoundlessContract, BoundlessContractClient,
 };
 use soroban_sdk::{
+    contract, contractimpl,
     testutils::{Address as _, Events, Ledger},
     token::{StellarAssetClient, TokenClient},
-    Address, Env, String, Vec,
+    vec, Address, BytesN, Env, String, Vec,
 };
 
 fn setup_test_env<'a>() -> (
@@ -65,8 +67,10 @@ fn test_successful_funding() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     // Set project status to funding
@@ -90,7 +94,7 @@ fn test_successful_funding() {
 
     // Fund project with a valid amount
     let funding_amount = 200000_i128;
-    let result = client.fund_project(&project_id, &funding_amount, &user, &token.address);
+    let result = client.fund_project(&project_id, &funding_amount, &user, &token.address, &None);
     assert_eq!(result, ());
 
     // Check if the funding amounts are correct
@@ -144,8 +148,10 @@ fn test_funding_insufficient_funds() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     env.as_contract(&contract_id, || {
@@ -164,7 +170,8 @@ fn test_funding_insufficient_funds() {
 
     // Attempt to fund project with insufficient funds
     let funding_amount = 0_i128;
-    let result = client.try_fund_project(&project_id, &funding_amount, &user, &token.address);
+    let result =
+        client.try_fund_project(&project_id, &funding_amount, &user, &token.address, &None);
     assert_eq!(
         result,
         Err(Ok(BoundlessError::InsufficientFunds)),
@@ -172,6 +179,36 @@ fn test_funding_insufficient_funds() {
     );
 }
 
+#[test]
+fn test_funding_amount_too_large() {
+    let (env, contract_id, token_admin, token, admin, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_id, metadata_uri, funding_target, milestone_count) =
+        setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &milestone_count,
+        &FundingModel::AllOrNothing,
+    );
+    set_project_status(&env, &contract_id, &project_id, ProjectStatus::Funding);
+    client.whitelist_token_contract(&admin, &project_id, &token.address);
+
+    // The backer could pay it, but the amount would not fit the accounting
+    let backer = Address::generate(&env);
+    let amount = u64::MAX as i128 + 1;
+    token_admin.mint(&backer, &amount);
+    assert_eq!(
+        client.try_fund_project(&project_id, &amount, &backer, &token.address, &None),
+        Err(Ok(BoundlessError::AmountTooLarge))
+    );
+    assert_eq!(token.balance(&backer), amount);
+}
+
 #[test]
 fn test_funding_invalid_project_status() {
     let (env, contract_id, _, token, admin, user) = setup_test_env();
@@ -184,15 +221,18 @@ fn test_funding_invalid_project_status() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     client.whitelist_token_contract(&admin, &project_id, &token.address);
 
     // Attempt to fund project while in Voting stage
     let funding_amount = 200000_i128;
-    let result = client.try_fund_project(&project_id, &funding_amount, &user, &token.address);
+    let result =
+        client.try_fund_project(&project_id, &funding_amount, &user, &token.address, &None);
     assert_eq!(
         result,
         Err(Ok(BoundlessError::InvalidOperation)),
@@ -212,8 +252,10 @@ fn test_funding_project_closed() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     client.whitelist_token_contract(&admin, &project_id, &token.address);
@@ -233,7 +275,8 @@ fn test_funding_project_closed() {
 
     // Attempt to fund project when project is closed
     let funding_amount = 200000_i128;
-    let result = client.try_fund_project(&project_id, &funding_amount, &user, &token.address);
+    let result =
+        client.try_fund_project(&project_id, &funding_amount, &user, &token.address, &None);
     assert_eq!(
         result,
         Err(Ok(BoundlessError::ProjectClosed)),
@@ -253,8 +296,10 @@ fn test_funding_past_deadline() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     client.whitelist_token_contract(&admin, &project_id, &token.address);
@@ -278,7 +323,8 @@ fn test_funding_past_deadline() {
 
     // Attempt to fund project when funding deadline is reached
     let funding_amount = 200000_i128;
-    let result = client.try_fund_project(&project_id, &funding_amount, &user, &token.address);
+    let result =
+        client.try_fund_project(&project_id, &funding_amount, &user, &token.address, &None);
     assert_eq!(
         result,
         Err(Ok(BoundlessError::FundingPeriodEnded)),
@@ -298,8 +344,10 @@ fn test_funding_invalid_token() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     env.as_contract(&contract_id, || {
@@ -316,7 +364,8 @@ fn test_funding_invalid_token() {
 
     // Attempt to fund project without whitelisting token contract
     let funding_amount = 200000_i128;
-    let result = client.try_fund_project(&project_id, &funding_amount, &user, &token.address);
+    let result =
+        client.try_fund_project(&project_id, &funding_amount, &user, &token.address, &None);
     assert_eq!(
         result,
         Err(Ok(BoundlessError::InvalidTokenContract)),
@@ -336,8 +385,10 @@ fn test_funding_same_backer_multiple_contributions() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     client.whitelist_token_contract(&admin, &project_id, &token.address);
@@ -356,8 +407,8 @@ fn test_funding_same_backer_multiple_contributions() {
 
     let funding_amount1 = 200000_i128;
     let funding_amount2 = 100000_i128;
-    client.fund_project(&project_id, &funding_amount1, &user, &token.address);
-    client.fund_project(&project_id, &funding_amount2, &user, &token.address);
+    client.fund_project(&project_id, &funding_amount1, &user, &token.address, &None);
+    client.fund_project(&project_id, &funding_amount2, &user, &token.address, &None);
 
     let funding_total = (funding_amount1 + funding_amount2) as u64;
     let (total_funded, _) = client.get_project_funding(&project_id);
@@ -409,8 +460,10 @@ fn test_fund_same_backer_multiple_tokens() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     // Whitelist both token contracts
@@ -440,8 +493,20 @@ fn test_fund_same_backer_multiple_tokens() {
     let funding_amount = 250000_i128;
     let funding_total = funding_amount * 2;
 
-    client.fund_project(&project_id, &funding_amount, &user, &token_client1.address);
-    client.fund_project(&project_id, &funding_amount, &user, &token_client2.address);
+    client.fund_project(
+        &project_id,
+        &funding_amount,
+        &user,
+        &token_client1.address,
+        &None,
+    );
+    client.fund_project(
+        &project_id,
+        &funding_amount,
+        &user,
+        &token_client2.address,
+        &None,
+    );
 
     // Compare token balances after funding
     let user_token1_balance_after_funding: i128 = token_client1.balance(&user);
@@ -480,8 +545,10 @@ fn test_funding_multiple_backers() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     client.whitelist_token_contract(&admin, &project_id, &token.address);
@@ -500,8 +567,14 @@ fn test_funding_multiple_backers() {
 
     let funding_amount1 = 200000_i128;
     let funding_amount2 = 250000_i128;
-    client.fund_project(&project_id, &funding_amount1, &user, &token.address);
-    client.fund_project(&project_id, &funding_amount2, &backer, &token.address);
+    client.fund_project(&project_id, &funding_amount1, &user, &token.address, &None);
+    client.fund_project(
+        &project_id,
+        &funding_amount2,
+        &backer,
+        &token.address,
+        &None,
+    );
 
     let (total_funded, _) = client.get_project_funding(&project_id);
     assert_eq!(total_funded, (funding_amount1 + funding_amount2) as u64);
@@ -550,8 +623,10 @@ fn test_funding_multiple_contributions_hit_target() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     client.whitelist_token_contract(&admin, &project_id, &token.address);
@@ -574,8 +649,14 @@ fn test_funding_multiple_contributions_hit_target() {
 
     let funding_amount1 = 250000_i128;
     let funding_amount2 = 250000_i128;
-    client.fund_project(&project_id, &funding_amount1, &user, &token.address);
-    client.fund_project(&project_id, &funding_amount2, &backer, &token.address);
+    client.fund_project(&project_id, &funding_amount1, &user, &token.address, &None);
+    client.fund_project(
+        &project_id,
+        &funding_amount2,
+        &backer,
+        &token.address,
+        &None,
+    );
 
     let funding_total = (funding_amount1 + funding_amount2) as u64;
     let (total_funded, target) = client.get_project_funding(&project_id);
@@ -620,8 +701,10 @@ fn test_refund_scenario() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     client.whitelist_token_contract(&admin, &project_id, &token.address);
@@ -639,7 +722,7 @@ fn test_refund_scenario() {
     });
 
     let funding_amount = 200000_i128;
-    client.fund_project(&project_id, &funding_amount, &user, &token.address);
+    client.fund_project(&project_id, &funding_amount, &user, &token.address, &None);
 
     env.as_contract(&contract_id, || {
         let mut project: Project = env
@@ -689,8 +772,10 @@ fn test_refund_invalid_project_status() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     client.whitelist_token_contract(&admin, &project_id, &token.address);
@@ -708,7 +793,7 @@ fn test_refund_invalid_project_status() {
     });
 
     let funding_amount = 200000_i128;
-    client.fund_project(&project_id, &funding_amount, &user, &token.address);
+    client.fund_project(&project_id, &funding_amount, &user, &token.address, &None);
 
     // Attempt to refund without closing project or failed project status
     let result = client.try_refund(&project_id, &token.address);
@@ -731,8 +816,10 @@ fn test_refund_non_whitelisted_token() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     client.whitelist_token_contract(&admin, &project_id, &token.address);
@@ -750,7 +837,7 @@ fn test_refund_non_whitelisted_token() {
     });
 
     let funding_amount = 200000_i128;
-    client.fund_project(&project_id, &funding_amount, &user, &token.address);
+    client.fund_project(&project_id, &funding_amount, &user, &token.address, &None);
 
     env.as_contract(&contract_id, || {
         let mut project: Project = env
@@ -786,8 +873,10 @@ fn test_refund_already_processed() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     client.whitelist_token_contract(&admin, &project_id, &token.address);
@@ -805,7 +894,7 @@ fn test_refund_already_processed() {
     });
 
     let funding_amount = 200000_i128;
-    client.fund_project(&project_id, &funding_amount, &user, &token.address);
+    client.fund_project(&project_id, &funding_amount, &user, &token.address, &None);
 
     env.as_contract(&contract_id, || {
         let mut project: Project = env
@@ -843,8 +932,10 @@ fn test_refund_no_backer_contributions() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     client.whitelist_token_contract(&admin, &project_id, &token.address);
@@ -893,8 +984,10 @@ fn test_refund_insufficient_funds() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     client.whitelist_token_contract(&admin, &project_id, &token.address);
@@ -912,7 +1005,7 @@ fn test_refund_insufficient_funds() {
     });
 
     let funding_amount = 200000_i128;
-    client.fund_project(&project_id, &funding_amount, &user, &token.address);
+    client.fund_project(&project_id, &funding_amount, &user, &token.address, &None);
 
     env.as_contract(&contract_id, || {
         let mut project: Project = env
@@ -953,8 +1046,10 @@ fn test_refund_multiple_backers() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     client.whitelist_token_contract(&admin, &project_id, &token.address);
@@ -981,8 +1076,20 @@ fn test_refund_multiple_backers() {
     let funding_total = funding_amount1 + funding_amount2;
 
     // Fund project with multiple backers
-    client.fund_project(&project_id, &funding_amount1, &backer1, &token.address);
-    client.fund_project(&project_id, &funding_amount2, &backer2, &token.address);
+    client.fund_project(
+        &project_id,
+        &funding_amount1,
+        &backer1,
+        &token.address,
+        &None,
+    );
+    client.fund_project(
+        &project_id,
+        &funding_amount2,
+        &backer2,
+        &token.address,
+        &None,
+    );
 
     // Compare token balances after funding
     let backer1_balance_after_funding: i128 = token.balance(&backer1);
@@ -1076,8 +1183,10 @@ fn test_refund_multiple_tokens() {
         &project_id,
         &user,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 
     // Whitelist both token contracts
@@ -1115,24 +1224,28 @@ fn test_refund_multiple_tokens() {
         &funding_amount,
         &backer1,
         &token_client1.address,
+        &None,
     );
     client.fund_project(
         &project_id,
         &funding_amount,
         &backer2,
         &token_client1.address,
+        &None,
     );
     client.fund_project(
         &project_id,
         &funding_amount,
         &backer1,
         &token_client2.address,
+        &None,
     );
     client.fund_project(
         &project_id,
         &funding_amount,
         &backer2,
         &token_client2.address,
+        &None,
     );
 
     // Compare token balances after funding
@@ -1256,3 +1369,991 @@ fn test_refund_multiple_tokens() {
         assert_eq!(refunded_tokens.get(1).unwrap(), token_client2.address);
     });
 }
+
+fn set_project_status(
+    env: &Env,
+    contract_id: &Address,
+    project_id: &String,
+    status: ProjectStatus,
+) {
+    env.as_contract(contract_id, || {
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .unwrap();
+        project.status = status;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+    });
+}
+
+#[test]
+fn test_claim_upfront() {
+    let (env, contract_id, token_admin, token, admin, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_id, metadata_uri, funding_target, milestone_count) =
+        setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &milestone_count,
+        &FundingModel::AllOrNothing,
+    );
+    client.set_upfront_bps(&project_id, &creator, &1000);
+
+    set_project_status(&env, &contract_id, &project_id, ProjectStatus::Funding);
+    client.whitelist_token_contract(&admin, &project_id, &token.address);
+    let backer = Address::generate(&env);
+    token_admin.mint(&backer, &(funding_target as i128));
+    client.fund_project(
+        &project_id,
+        &(funding_target as i128),
+        &backer,
+        &token.address,
+        &None,
+    );
+
+    let creator_balance_before = token.balance(&creator);
+    assert_eq!(client.claim_upfront(&project_id), 50000);
+    assert_eq!(token.balance(&creator), creator_balance_before + 50000);
+    assert_eq!(
+        client.get_upfront_tranche(&project_id).claimed_amount,
+        50000
+    );
+    assert_eq!(
+        client.try_claim_upfront(&project_id),
+        Err(Ok(BoundlessError::AlreadyClaimed))
+    );
+
+    // Refunds only cover what is left in escrow after the tranche
+    set_project_status(&env, &contract_id, &project_id, ProjectStatus::Failed);
+    client.refund(&project_id, &token.address);
+    assert_eq!(token.balance(&backer), 450000);
+}
+
+#[test]
+fn test_claim_upfront_before_funded() {
+    let (env, contract_id, _, _, _, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_id, metadata_uri, funding_target, milestone_count) =
+        setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &milestone_count,
+        &FundingModel::AllOrNothing,
+    );
+    client.set_upfront_bps(&project_id, &creator, &1000);
+
+    assert_eq!(
+        client.try_claim_upfront(&project_id),
+        Err(Ok(BoundlessError::InvalidOperation))
+    );
+}
+
+#[test]
+fn test_set_upfront_bps_above_cap() {
+    let (env, contract_id, _, _, _, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_id, metadata_uri, funding_target, milestone_count) =
+        setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &milestone_count,
+        &FundingModel::AllOrNothing,
+    );
+
+    let upfront_bps = client.get_config().max_upfront_bps + 1;
+    assert_eq!(
+        client.try_set_upfront_bps(&project_id, &creator, &upfront_bps),
+        Err(Ok(BoundlessError::InvalidShare))
+    );
+}
+
+#[test]
+fn test_fund_project_with_reward_tier() {
+    let (env, contract_id, token_admin, token, admin, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_id, metadata_uri, funding_target, milestone_count) =
+        setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &milestone_count,
+        &FundingModel::AllOrNothing,
+    );
+    let tier_id = client.add_reward_tier(
+        &project_id,
+        &creator,
+        &String::from_str(&env, "Early bird"),
+        &String::from_str(&env, "ipfs://tier"),
+        &100,
+        &vec![
+            &env,
+            TierPrice {
+                token: token.address.clone(),
+                min_amount: 200,
+            },
+        ],
+        &Some(1),
+    );
+    assert_eq!(tier_id, 0);
+    assert_eq!(client.get_tier_remaining(&project_id, &tier_id), Some(1));
+
+    set_project_status(&env, &contract_id, &project_id, ProjectStatus::Funding);
+    client.whitelist_token_contract(&admin, &project_id, &token.address);
+    let backer = Address::generate(&env);
+    token_admin.mint(&backer, &1000);
+
+    // The token-specific price overrides the default minimum pledge
+    assert_eq!(
+        client.try_fund_project(&project_id, &150, &backer, &token.address, &Some(tier_id)),
+        Err(Ok(BoundlessError::PledgeBelowTierMinimum))
+    );
+    client.fund_project(&project_id, &200, &backer, &token.address, &Some(tier_id));
+    assert_eq!(client.get_tier_remaining(&project_id, &tier_id), Some(0));
+    assert_eq!(client.get_backer_tiers(&project_id, &backer), vec![&env, 0]);
+    assert_eq!(
+        client
+            .get_reward_tiers(&project_id)
+            .get_unchecked(0)
+            .claimed,
+        1
+    );
+
+    assert_eq!(
+        client.try_fund_project(&project_id, &200, &backer, &token.address, &Some(tier_id)),
+        Err(Ok(BoundlessError::TierSoldOut))
+    );
+    assert_eq!(
+        client.try_fund_project(&project_id, &200, &backer, &token.address, &Some(1)),
+        Err(Ok(BoundlessError::TierNotFound))
+    );
+    assert_eq!(token.balance(&backer), 800);
+}
+
+#[test]
+fn test_add_reward_tier_after_publishing() {
+    let (env, contract_id, _, _, _, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_id, metadata_uri, funding_target, milestone_count) =
+        setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &milestone_count,
+        &FundingModel::AllOrNothing,
+    );
+    set_project_status(&env, &contract_id, &project_id, ProjectStatus::Funding);
+
+    let result = client.try_add_reward_tier(
+        &project_id,
+        &creator,
+        &String::from_str(&env, "Early bird"),
+        &String::from_str(&env, "ipfs://tier"),
+        &100,
+        &Vec::new(&env),
+        &None,
+    );
+    assert_eq!(result, Err(Ok(BoundlessError::InvalidOperation)));
+}
+
+#[test]
+fn test_stretch_goal_unlocks_milestones() {
+    let (env, contract_id, token_admin, token, admin, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_id, metadata_uri, funding_target, milestone_count) =
+        setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &milestone_count,
+        &FundingModel::AllOrNothing,
+    );
+    let stretch_milestone = MilestonePlanItem {
+        description: String::from_str(&env, "stretch"),
+        amount: 100000,
+        due_date: 1000,
+    };
+    let result = client.try_add_stretch_goal(
+        &project_id,
+        &creator,
+        &(funding_target + 50000),
+        &String::from_str(&env, "Soundtrack"),
+        &vec![&env, stretch_milestone.clone()],
+    );
+    assert_eq!(result, Err(Ok(BoundlessError::InvalidMilestone)));
+    client.add_stretch_goal(
+        &project_id,
+        &creator,
+        &(funding_target + 100000),
+        &String::from_str(&env, "Soundtrack"),
+        &vec![&env, stretch_milestone],
+    );
+
+    set_project_status(&env, &contract_id, &project_id, ProjectStatus::Funding);
+    client.whitelist_token_contract(&admin, &project_id, &token.address);
+    let backer = Address::generate(&env);
+    token_admin.mint(&backer, &1000000);
+    client.fund_project(
+        &project_id,
+        &(funding_target as i128),
+        &backer,
+        &token.address,
+        &None,
+    );
+    assert_eq!(
+        client.get_project_status(&project_id),
+        ProjectStatus::Funded
+    );
+    assert!(client
+        .get_stretch_goals(&project_id)
+        .get_unchecked(0)
+        .reached_at
+        .is_none());
+
+    // Funding continues past the target until the stretch goal is reached
+    client.fund_project(&project_id, &100000, &backer, &token.address, &None);
+    assert!(client
+        .get_stretch_goals(&project_id)
+        .get_unchecked(0)
+        .reached_at
+        .is_some());
+    let project = client.get_project(&project_id);
+    assert_eq!(project.milestones.len(), 1);
+    assert_eq!(project.milestones.get_unchecked(0).amount, 100000);
+    assert_eq!(
+        client.try_fund_project(&project_id, &1000, &backer, &token.address, &None),
+        Err(Ok(BoundlessError::InvalidOperation))
+    );
+}
+
+#[test]
+fn test_finalize_funding_below_target() {
+    let (env, contract_id, token_admin, token, admin, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (_, metadata_uri, funding_target, milestone_count) = setup_create_project_args(&env);
+    let all_or_nothing = String::from_str(&env, "all-or-nothing");
+    let keep_it_all = String::from_str(&env, "keep-it-all");
+    let backer = Address::generate(&env);
+    token_admin.mint(&backer, &2000);
+    for (project_id, funding_model) in [
+        (&all_or_nothing, FundingModel::AllOrNothing),
+        (&keep_it_all, FundingModel::KeepItAll),
+    ] {
+        client.create_project(
+            project_id,
+            &creator,
+            &metadata_uri,
+            &BytesN::from_array(&env, &[0; 32]),
+            &funding_target,
+            &milestone_count,
+            &funding_model,
+        );
+        set_project_status(&env, &contract_id, project_id, ProjectStatus::Funding);
+        client.whitelist_token_contract(&admin, project_id, &token.address);
+        client.fund_project(project_id, &1000, &backer, &token.address, &None);
+        assert_eq!(
+            client.try_finalize_funding(project_id),
+            Err(Ok(BoundlessError::InvalidOperation))
+        );
+    }
+
+    env.ledger()
+        .set_timestamp(FUNDING_PERIOD_LEDGERS as u64 + 1);
+    assert_eq!(
+        client.finalize_funding(&all_or_nothing),
+        ProjectStatus::Failed
+    );
+    assert_eq!(client.finalize_funding(&keep_it_all), ProjectStatus::Funded);
+
+    client.refund(&all_or_nothing, &token.address);
+    assert_eq!(token.balance(&backer), 1000);
+    assert_eq!(
+        client.try_refund(&keep_it_all, &token.address),
+        Err(Ok(BoundlessError::InvalidOperation))
+    );
+}
+
+#[test]
+fn test_claim_donations() {
+    let (env, contract_id, token_admin, token, admin, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+    let mut config = client.get_config();
+    config.donation_fee_bps = 100;
+    client.set_config(&admin, &config);
+
+    let (project_id, metadata_uri, funding_target, _) = setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &0,
+        &FundingModel::Donation,
+    );
+    let result = client.try_add_milestone(
+        &project_id,
+        &creator,
+        &String::from_str(&env, "milestone"),
+        &1000,
+        &1000,
+    );
+    assert_eq!(result, Err(Ok(BoundlessError::InvalidOperation)));
+
+    set_project_status(&env, &contract_id, &project_id, ProjectStatus::Funding);
+    client.whitelist_token_contract(&admin, &project_id, &token.address);
+    let backer = Address::generate(&env);
+    token_admin.mint(&backer, &10000);
+    client.fund_project(&project_id, &10000, &backer, &token.address, &None);
+
+    let creator_balance_before = token.balance(&creator);
+    assert_eq!(client.claim_donations(&project_id, &token.address), 9900);
+    assert_eq!(token.balance(&creator), creator_balance_before + 9900);
+    assert_eq!(token.balance(&admin), 100);
+    assert_eq!(
+        client.try_claim_donations(&project_id, &token.address),
+        Err(Ok(BoundlessError::InsufficientFunds))
+    );
+    assert_eq!(
+        client.try_refund(&project_id, &token.address),
+        Err(Ok(BoundlessError::InvalidOperation))
+    );
+}
+
+#[test]
+fn test_force_failed_donations_refund_unclaimed() {
+    let (env, contract_id, token_admin, token, admin, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_id, metadata_uri, funding_target, _) = setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &0,
+        &FundingModel::Donation,
+    );
+    set_project_status(&env, &contract_id, &project_id, ProjectStatus::Funding);
+    client.whitelist_token_contract(&admin, &project_id, &token.address);
+    let early_backer = Address::generate(&env);
+    token_admin.mint(&early_backer, &6000);
+    client.fund_project(&project_id, &6000, &early_backer, &token.address, &None);
+    client.claim_donations(&project_id, &token.address);
+
+    let late_backer = Address::generate(&env);
+    token_admin.mint(&late_backer, &4000);
+    client.fund_project(&project_id, &4000, &late_backer, &token.address, &None);
+    client.force_fail_project(&admin, &project_id);
+
+    // Only the unclaimed donations are left, shared pro rata
+    client.refund(&project_id, &token.address);
+    assert_eq!(token.balance(&early_backer), 2400);
+    assert_eq!(token.balance(&late_backer), 1600);
+    assert_eq!(client.get_escrow_balance(&project_id, &token.address), 0);
+}
+
+fn pledge_with_allowance(
+    client: &BoundlessContractClient,
+    token_admin: &StellarAssetClient,
+    token: &TokenClient,
+    project_id: &String,
+    backer: &Address,
+    amount: i128,
+) {
+    token_admin.mint(backer, &amount);
+    token.approve(backer, &client.address, &amount, &100000);
+    client.fund_project(project_id, &amount, backer, &token.address, &None);
+}
+
+#[test]
+fn test_settle_deferred_pledges() {
+    let (env, contract_id, token_admin, token, admin, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_id, metadata_uri, funding_target, milestone_count) =
+        setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &milestone_count,
+        &FundingModel::AllOrNothing,
+    );
+    client.set_deferred_pledges(&project_id, &creator, &true);
+    let tier_id = client.add_reward_tier(
+        &project_id,
+        &creator,
+        &String::from_str(&env, "Early bird"),
+        &String::from_str(&env, "ipfs://tier"),
+        &100,
+        &Vec::new(&env),
+        &Some(1),
+    );
+    set_project_status(&env, &contract_id, &project_id, ProjectStatus::Funding);
+    client.whitelist_token_contract(&admin, &project_id, &token.address);
+
+    let backer1 = Address::generate(&env);
+    let backer2 = Address::generate(&env);
+    let backer3 = Address::generate(&env);
+    pledge_with_allowance(&client, &token_admin, &token, &project_id, &backer1, 300000);
+    pledge_with_allowance(&client, &token_admin, &token, &project_id, &backer2, 200000);
+    token_admin.mint(&backer3, &100000);
+    token.approve(&backer3, &client.address, &100000, &100000);
+    client.fund_project(
+        &project_id,
+        &100000,
+        &backer3,
+        &token.address,
+        &Some(tier_id),
+    );
+    assert_eq!(client.get_tier_remaining(&project_id, &tier_id), Some(0));
+    assert_eq!(
+        client.try_fund_project(&project_id, &1, &backer1, &token.address, &None),
+        Err(Ok(BoundlessError::InsufficientFunds))
+    );
+    assert_eq!(client.get_pledges(&project_id).len(), 3);
+    assert_eq!(token.balance(&contract_id), 0);
+    assert_eq!(
+        client.try_settle_pledges(&project_id, &0, &10),
+        Err(Ok(BoundlessError::InvalidOperation))
+    );
+
+    // The third backer revokes their allowance before settlement
+    token.approve(&backer3, &contract_id, &0, &100000);
+    env.ledger()
+        .set_timestamp(FUNDING_PERIOD_LEDGERS as u64 + 1);
+    assert_eq!(client.settle_pledges(&project_id, &0, &2).len(), 0);
+    assert_eq!(
+        client.get_project_status(&project_id),
+        ProjectStatus::Funding
+    );
+    assert_eq!(
+        client.try_settle_pledges(&project_id, &0, &2),
+        Err(Ok(BoundlessError::InvalidOperation))
+    );
+    let failed_pulls = client.settle_pledges(&project_id, &2, &2);
+    assert_eq!(failed_pulls.len(), 1);
+    assert_eq!(failed_pulls.get_unchecked(0).backer, backer3);
+    assert_eq!(client.get_tier_remaining(&project_id, &tier_id), Some(1));
+    assert_eq!(client.get_backer_tiers(&project_id, &backer3).len(), 0);
+
+    assert_eq!(
+        client.get_project_status(&project_id),
+        ProjectStatus::Funded
+    );
+    assert_eq!(client.get_project(&project_id).total_funded, 500000);
+    assert_eq!(token.balance(&contract_id), 500000);
+    assert_eq!(token.balance(&backer3), 100000);
+    assert_eq!(client.get_pledges(&project_id).len(), 0);
+}
+
+#[test]
+fn test_settle_deferred_pledges_below_target() {
+    let (env, contract_id, token_admin, token, admin, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_id, metadata_uri, funding_target, milestone_count) =
+        setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &milestone_count,
+        &FundingModel::AllOrNothing,
+    );
+    client.set_deferred_pledges(&project_id, &creator, &true);
+    set_project_status(&env, &contract_id, &project_id, ProjectStatus::Funding);
+    client.whitelist_token_contract(&admin, &project_id, &token.address);
+
+    let backer = Address::generate(&env);
+    pledge_with_allowance(&client, &token_admin, &token, &project_id, &backer, 100000);
+    env.ledger()
+        .set_timestamp(FUNDING_PERIOD_LEDGERS as u64 + 1);
+    assert_eq!(
+        client.try_finalize_funding(&project_id),
+        Err(Ok(BoundlessError::InvalidOperation))
+    );
+    assert_eq!(client.settle_pledges(&project_id, &0, &10).len(), 0);
+
+    assert_eq!(
+        client.get_project_status(&project_id),
+        ProjectStatus::Failed
+    );
+    assert_eq!(token.balance(&backer), 100000);
+    assert_eq!(client.get_project(&project_id).total_funded, 0);
+}
+
+#[test]
+fn test_pledges_require_backer_approval() {
+    let (env, contract_id, token_admin, token, admin, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_id, metadata_uri, funding_target, milestone_count) =
+        setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &milestone_count,
+        &FundingModel::AllOrNothing,
+    );
+    client.set_deferred_pledges(&project_id, &creator, &true);
+    set_project_status(&env, &contract_id, &project_id, ProjectStatus::Funding);
+    client.whitelist_token_contract(&admin, &project_id, &token.address);
+
+    let backer = Address::generate(&env);
+    pledge_with_allowance(&client, &token_admin, &token, &project_id, &backer, 900);
+    assert_eq!(client.get_project(&project_id).total_funded, 0);
+
+    // Pledges count as funding, so neither change applies on its own
+    let rugged = String::from_str(&env, "rugged");
+    client.update_project_metadata(
+        &project_id,
+        &creator,
+        &rugged,
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+    assert_eq!(client.get_project(&project_id).metadata_uri, metadata_uri);
+    assert_eq!(client.get_metadata_proposal(&project_id).uri, rugged);
+
+    let recipient = Address::generate(&env);
+    let splits = vec![
+        &env,
+        PayoutSplit {
+            recipient: recipient.clone(),
+            bps: 10000,
+        },
+    ];
+    client.set_payout_splits(&project_id, &creator, &splits);
+    assert_eq!(
+        client
+            .get_payout_splits(&project_id)
+            .get_unchecked(0)
+            .recipient,
+        creator
+    );
+
+    // Pledgers vote with the amount they pledged
+    assert_eq!(
+        client.try_vote_payout_splits(&project_id, &Address::generate(&env), &true),
+        Err(Ok(BoundlessError::NoBackerContributions))
+    );
+    client.vote_metadata_update(&project_id, &backer, &false);
+    assert!(!client.finalize_metadata_update(&project_id));
+    client.vote_payout_splits(&project_id, &backer, &true);
+    assert!(client.finalize_payout_splits(&project_id));
+    assert_eq!(client.get_project(&project_id).metadata_uri, metadata_uri);
+    assert_eq!(client.get_payout_splits(&project_id), splits);
+}
+
+#[test]
+fn test_recurring_pledge_charges_once_per_period() {
+    let (env, contract_id, token_admin, token, admin, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_id, metadata_uri, funding_target, _) = setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &0,
+        &FundingModel::Donation,
+    );
+    set_project_status(&env, &contract_id, &project_id, ProjectStatus::Funded);
+    client.whitelist_token_contract(&admin, &project_id, &token.address);
+
+    let backer = Address::generate(&env);
+    let period = 1000u64;
+    token_admin.mint(&backer, &30000);
+    token.approve(&backer, &contract_id, &30000, &100000);
+    client.create_recurring_pledge(&project_id, &backer, &token.address, &10000, &period);
+    assert_eq!(
+        client.try_create_recurring_pledge(&project_id, &backer, &token.address, &10000, &period),
+        Err(Ok(BoundlessError::AlreadyExists))
+    );
+
+    // Anyone can trigger the charge once it is due, but only once per period
+    assert_eq!(client.charge_recurring_pledge(&project_id, &backer), 10000);
+    assert_eq!(
+        client.try_charge_recurring_pledge(&project_id, &backer),
+        Err(Ok(BoundlessError::InvalidOperation))
+    );
+    env.ledger().set_timestamp(period);
+    client.charge_recurring_pledge(&project_id, &backer);
+
+    assert_eq!(token.balance(&contract_id), 20000);
+    assert_eq!(client.get_recurring_revenue(&project_id), 20000);
+    assert_eq!(client.get_backer_contribution(&project_id, &backer), 20000);
+    let pledge = client.get_recurring_pledge(&project_id, &backer);
+    assert_eq!(pledge.total_charged, 20000);
+    assert_eq!(pledge.next_charge_at, 2 * period);
+
+    client.cancel_recurring_pledge(&project_id, &backer);
+    env.ledger().set_timestamp(2 * period);
+    assert_eq!(
+        client.try_charge_recurring_pledge(&project_id, &backer),
+        Err(Ok(BoundlessError::InvalidOperation))
+    );
+    assert_eq!(token.balance(&backer), 10000);
+    assert_eq!(client.get_recurring_revenue(&project_id), 20000);
+    assert_eq!(client.claim_donations(&project_id, &token.address), 20000);
+}
+
+#[test]
+fn test_recurring_pledge_requires_donation_project() {
+    let (env, contract_id, token_admin, token, admin, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_id, metadata_uri, funding_target, milestone_count) =
+        setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &milestone_count,
+        &FundingModel::AllOrNothing,
+    );
+    set_project_status(&env, &contract_id, &project_id, ProjectStatus::Funded);
+    client.whitelist_token_contract(&admin, &project_id, &token.address);
+
+    // Milestone plans are fixed once funded, so nothing could release the charges
+    let backer = Address::generate(&env);
+    token_admin.mint(&backer, &10000);
+    token.approve(&backer, &contract_id, &10000, &100000);
+    assert_eq!(
+        client.try_create_recurring_pledge(&project_id, &backer, &token.address, &10000, &1000),
+        Err(Ok(BoundlessError::InvalidOperation))
+    );
+}
+
+#[test]
+fn test_recurring_pledge_fails_without_allowance() {
+    let (env, contract_id, token_admin, token, admin, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_id, metadata_uri, funding_target, _) = setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &0,
+        &FundingModel::Donation,
+    );
+    set_project_status(&env, &contract_id, &project_id, ProjectStatus::Funded);
+    client.whitelist_token_contract(&admin, &project_id, &token.address);
+
+    let backer = Address::generate(&env);
+    token_admin.mint(&backer, &10000);
+    assert_eq!(
+        client.try_create_recurring_pledge(&project_id, &backer, &token.address, &10000, &1000),
+        Err(Ok(BoundlessError::InsufficientFunds))
+    );
+
+    token.approve(&backer, &contract_id, &10000, &100000);
+    client.create_recurring_pledge(&project_id, &backer, &token.address, &10000, &1000);
+    token.approve(&backer, &contract_id, &0, &100000);
+    assert_eq!(
+        client.try_charge_recurring_pledge(&project_id, &backer),
+        Err(Ok(BoundlessError::TransferFailed))
+    );
+    assert_eq!(client.get_recurring_revenue(&project_id), 0);
+}
+
+#[test]
+fn test_fund_project_for_beneficiary() {
+    let (env, contract_id, token_admin, token, admin, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_id, metadata_uri, funding_target, milestone_count) =
+        setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &milestone_count,
+        &FundingModel::AllOrNothing,
+    );
+    let tier_id = client.add_reward_tier(
+        &project_id,
+        &creator,
+        &String::from_str(&env, "Gift"),
+        &String::from_str(&env, "ipfs://tier"),
+        &100,
+        &Vec::new(&env),
+        &None,
+    );
+    set_project_status(&env, &contract_id, &project_id, ProjectStatus::Funding);
+    client.whitelist_token_contract(&admin, &project_id, &token.address);
+
+    let payer = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    token_admin.mint(&payer, &100000);
+    client.fund_project_for(
+        &project_id,
+        &payer,
+        &beneficiary,
+        &100000,
+        &token.address,
+        &Some(tier_id),
+    );
+
+    assert_eq!(
+        client.get_backer_contribution(&project_id, &beneficiary),
+        100000
+    );
+    assert_eq!(client.get_backer_contribution(&project_id, &payer), 0);
+    assert_eq!(client.get_backer_tiers(&project_id, &beneficiary).len(), 1);
+    assert_eq!(client.get_backer_tiers(&project_id, &payer).len(), 0);
+
+    // The refund goes to the beneficiary, not the payer
+    env.ledger()
+        .set_timestamp(FUNDING_PERIOD_LEDGERS as u64 + 1);
+    client.finalize_funding(&project_id);
+    client.refund(&project_id, &token.address);
+    assert_eq!(token.balance(&beneficiary), 100000);
+    assert_eq!(token.balance(&payer), 0);
+}
+
+#[test]
+fn test_attested_contributions() {
+    let (env, contract_id, token_admin, token, admin, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_id, metadata_uri, funding_target, milestone_count) =
+        setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &milestone_count,
+        &FundingModel::AllOrNothing,
+    );
+    set_project_status(&env, &contract_id, &project_id, ProjectStatus::Funding);
+    client.whitelist_token_contract(&admin, &project_id, &token.address);
+
+    let attestor = Address::generate(&env);
+    let fiat_backer = Address::generate(&env);
+    let reference = String::from_str(&env, "anchor-tx-1");
+    assert_eq!(
+        client.try_attest_contribution(&attestor, &project_id, &reference, &fiat_backer, &50000),
+        Err(Ok(BoundlessError::Unauthorized))
+    );
+    client.add_attestor(&admin, &attestor);
+    assert!(client.attest_contribution(&attestor, &project_id, &reference, &fiat_backer, &50000));
+    // Resubmitting the same reference is a no-op
+    assert!(!client.attest_contribution(&attestor, &project_id, &reference, &fiat_backer, &50000));
+    assert_eq!(
+        client.get_attestation(&project_id, &reference).amount,
+        50000
+    );
+
+    let backer = Address::generate(&env);
+    token_admin.mint(&backer, &100000);
+    client.fund_project(&project_id, &100000, &backer, &token.address, &None);
+
+    assert_eq!(client.get_project(&project_id).total_funded, 150000);
+    assert_eq!(client.get_funding_breakdown(&project_id), (100000, 50000));
+    env.as_contract(&contract_id, || {
+        let project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .unwrap();
+        assert_eq!(contribution_weight(&project, &fiat_backer), 50000);
+    });
+
+    // Refunds only return tokens that were paid on-chain
+    env.ledger()
+        .set_timestamp(FUNDING_PERIOD_LEDGERS as u64 + 1);
+    client.finalize_funding(&project_id);
+    client.refund(&project_id, &token.address);
+    assert_eq!(token.balance(&backer), 100000);
+    assert_eq!(token.balance(&contract_id), 0);
+
+    client.remove_attestor(&admin, &attestor);
+    assert_eq!(client.get_attestors().len(), 0);
+}
+
+/// Token that burns a 10% fee on every transfer
+#[contract]
+struct FeeOnTransferToken;
+
+#[contractimpl]
+impl FeeOnTransferToken {
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage().persistent().set(&to, &(balance + amount));
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage().persistent().get(&id).unwrap_or(0)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        let from_balance = Self::balance(env.clone(), from.clone());
+        let to_balance = Self::balance(env.clone(), to.clone());
+        env.storage()
+            .persistent()
+            .set(&from, &(from_balance - amount));
+        env.storage()
+            .persistent()
+            .set(&to, &(to_balance + amount - amount / 10));
+    }
+}
+
+#[test]
+fn test_fund_project_credits_received_amount() {
+    let (env, contract_id, _, _, admin, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_id, metadata_uri, funding_target, milestone_count) =
+        setup_create_project_args(&env);
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &milestone_count,
+        &FundingModel::AllOrNothing,
+    );
+    set_project_status(&env, &contract_id, &project_id, ProjectStatus::Funding);
+
+    let token_id = env.register(FeeOnTransferToken, ());
+    let token = FeeOnTransferTokenClient::new(&env, &token_id);
+    client.whitelist_token_contract(&admin, &project_id, &token_id);
+
+    let backer = Address::generate(&env);
+    token.mint(&backer, &100000);
+    client.fund_project(&project_id, &100000, &backer, &token_id, &None);
+
+    assert_eq!(token.balance(&contract_id), 90000);
+    assert_eq!(client.get_project(&project_id).total_funded, 90000);
+    assert_eq!(client.get_backer_contribution(&project_id, &backer), 90000);
+
+    // Once flagged, the token is no longer accepted even though it is allowlisted
+    client.set_token_non_standard(&admin, &token_id, &true);
+    assert!(client.is_token_non_standard(&token_id));
+    assert_eq!(
+        client.try_fund_project(&project_id, &1000, &backer, &token_id, &None),
+        Err(Ok(BoundlessError::InvalidTokenContract))
+    );
+    let other_project = String::from_str(&env, "other-project");
+    client.create_project(
+        &other_project,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &milestone_count,
+        &FundingModel::AllOrNothing,
+    );
+    assert_eq!(
+        client.try_whitelist_token_contract(&admin, &other_project, &token_id),
+        Err(Ok(BoundlessError::InvalidTokenContract))
+    );
+}
+
+#[test]
+fn test_escrow_ledger_isolates_projects() {
+    let (env, contract_id, token_admin, token, admin, creator) = setup_test_env();
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    let (project_a, metadata_uri, funding_target, milestone_count) =
+        setup_create_project_args(&env);
+    let project_b = String::from_str(&env, "project-b");
+    for project_id in [&project_a, &project_b] {
+        client.create_project(
+            project_id,
+            &creator,
+            &metadata_uri,
+            &BytesN::from_array(&env, &[0; 32]),
+            &funding_target,
+            &milestone_count,
+            &FundingModel::AllOrNothing,
+        );
+        set_project_status(&env, &contract_id, project_id, ProjectStatus::Funding);
+        client.whitelist_token_contract(&admin, project_id, &token.address);
+    }
+
+    let backer = Address::generate(&env);
+    token_admin.mint(&backer, &300000);
+    client.fund_project(&project_a, &200000, &backer, &token.address, &None);
+    client.fund_project(&project_b, &100000, &backer, &token.address, &None);
+    assert_eq!(
+        client.get_escrow_balance(&project_a, &token.address),
+        200000
+    );
+    assert_eq!(
+        client.get_escrow_balance(&project_b, &token.address),
+        100000
+    );
+
+    // A project whose ledger falls short cannot dip into another project's funds
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(
+            &DataKey::Escrow(project_b.clone(), token.address.clone()),
+            &50000_u64,
+        );
+    });
+    set_project_status(&env, &contract_id, &project_b, ProjectStatus::Failed);
+    assert_eq!(
+        client.try_refund(&project_b, &token.address),
+        Err(Ok(BoundlessError::InsufficientFunds))
+    );
+
+    set_project_status(&env, &contract_id, &project_a, ProjectStatus::Failed);
+    client.refund(&project_a, &token.address);
+    assert_eq!(client.get_escrow_balance(&project_a, &token.address), 0);
+    assert_eq!(token.balance(&contract_id), 100000);
+    assert_eq!(token.balance(&backer), 200000);
+}
@@ -1,7 +1,9 @@
 #![cfg(test)]
 
 pub mod admin;
+pub mod audit;
 pub mod funding;
 pub mod milestone;
 pub mod project;
+pub mod team;
 pub mod voting;
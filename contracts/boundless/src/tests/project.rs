@@ -1,10 +1,17 @@
 #![cfg(test)]
 
 use crate::{
-    datatypes::{BoundlessError, Project, ProjectStatus},
+    datatypes::{
+        BondRequirement, BondStatus, BoundlessError, DataKey, FundingModel, PayoutSplit, Project,
+        ProjectStatus, SlashTarget, FUNDING_PERIOD_LEDGERS,
+    },
     BoundlessContract, BoundlessContractClient,
 };
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN, Env, String,
+};
 
 #[test]
 fn test_create_project() {
@@ -24,8 +31,10 @@ fn test_create_project() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let project = client.get_project(&project_id);
     assert_eq!(project.project_id, project_id);
@@ -53,15 +62,19 @@ fn test_create_project_already_exists() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     client.create_project(
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 }
 
@@ -82,8 +95,10 @@ fn test_create_project_invalid_funding_target() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 }
 
@@ -104,8 +119,10 @@ fn test_create_project_invalid_milestone_count() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
 }
 
@@ -125,13 +142,23 @@ fn test_update_project_metadata() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[1; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let new_metadata_uri = String::from_str(&env, "https://example.com/new_metadata");
-    client.update_project_metadata(&project_id, &creator, &new_metadata_uri);
+    let metadata_hash = BytesN::from_array(&env, &[3; 32]);
+    client.update_project_metadata(&project_id, &creator, &new_metadata_uri, &metadata_hash);
     let project = client.get_project(&project_id);
     assert_eq!(project.metadata_uri, new_metadata_uri);
+    let history = client.get_metadata_history(&project_id);
+    assert_eq!(history.len(), 2);
+    let initial = history.get_unchecked(0);
+    assert_eq!((initial.version, initial.uri), (1, metadata_uri));
+    assert_eq!(initial.hash, BytesN::from_array(&env, &[1; 32]));
+    assert_eq!(history.get_unchecked(1).version, 2);
+    assert_eq!(history.get_unchecked(1).hash, metadata_hash);
 }
 
 #[test]
@@ -152,11 +179,18 @@ fn test_update_project_metadata_unauthorized() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let unauthorized_address = Address::generate(&env);
-    client.update_project_metadata(&project_id, &unauthorized_address, &metadata_uri);
+    client.update_project_metadata(
+        &project_id,
+        &unauthorized_address,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[3; 32]),
+    );
 }
 
 #[test]
@@ -176,8 +210,10 @@ fn test_update_project_milestone_count() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let new_milestone_count = 10;
     client.update_project_milestone_count(&project_id, &creator, &new_milestone_count);
@@ -203,8 +239,10 @@ fn test_update_project_milestone_count_unauthorized() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let unauthorized_address = Address::generate(&env);
     client.update_project_milestone_count(&project_id, &unauthorized_address, &milestone_count);
@@ -227,8 +265,10 @@ fn test_modify_milestone() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let new_milestone_count = 10;
     client.modify_milestone(&project_id, &creator, &new_milestone_count);
@@ -254,9 +294,375 @@ fn test_modify_milestone_unauthorized() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let unauthorized_address = Address::generate(&env);
     client.modify_milestone(&project_id, &unauthorized_address, &milestone_count);
 }
+
+#[test]
+fn test_update_project_milestone_count_after_voting_started() {
+    let env = Env::default();
+    let contract_id = env.register(BoundlessContract, ());
+    let client = BoundlessContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    let creator = Address::generate(&env);
+    let project_id = String::from_str(&env, "test_project");
+    let metadata_uri = String::from_str(&env, "https://example.com/metadata");
+    let funding_target = 1000;
+    let milestone_count = 5;
+
+    client.create_project(
+        &project_id,
+        &creator,
+        &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
+        &funding_target,
+        &milestone_count,
+        &FundingModel::AllOrNothing,
+    );
+    let voter = Address::generate(&env);
+    client.vote_project(&project_id, &voter, &1);
+
+    let result = client.try_update_project_milestone_count(&project_id, &creator, &10);
+    assert_eq!(result, Err(Ok(BoundlessError::MilestonePlanLocked)));
+    let result = client.try_modify_milestone(&project_id, &creator, &10);
+    assert_eq!(result, Err(Ok(BoundlessError::MilestonePlanLocked)));
+}
+
+fn setup_bond_requirement<'a>(
+    env: &Env,
+    client: &BoundlessContractClient,
+) -> (TokenClient<'a>, Address) {
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    let stellar_asset = env.register_stellar_asset_contract_v2(admin.clone());
+    let token = TokenClient::new(env, &stellar_asset.address());
+    let treasury = Address::generate(env);
+    client.set_bond_requirement(
+        &admin,
+        &BondRequirement {
+            token: token.address.clone(),
+            amount: 100,
+            slash_target: SlashTarget::Backers,
+            treasury: treasury.clone(),
+        },
+    );
+    (token, treasury)
+}
+
+#[test]
+fn test_create_project_deposits_bond() {
+    let env = Env::default();
+    let contract_id = env.register(BoundlessContract, ());
+    let client = BoundlessContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    let (token, _) = setup_bond_requirement(&env, &client);
+
+    let creator = Address::generate(&env);
+    StellarAssetClient::new(&env, &token.address).mint(&creator, &100);
+    let project_id = String::from_str(&env, "test_project");
+    client.create_project(
+        &project_id,
+        &creator,
+        &String::from_str(&env, "https://example.com/metadata"),
+        &BytesN::from_array(&env, &[0; 32]),
+        &1000,
+        &5,
+        &FundingModel::AllOrNothing,
+    );
+
+    assert_eq!(token.balance(&creator), 0);
+    assert_eq!(token.balance(&contract_id), 100);
+    let bond = client.get_creator_bond(&project_id);
+    assert_eq!(bond.amount, 100);
+    assert_eq!(bond.status, BondStatus::Held);
+}
+
+#[test]
+fn test_create_project_without_bond_funds() {
+    let env = Env::default();
+    let contract_id = env.register(BoundlessContract, ());
+    let client = BoundlessContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    setup_bond_requirement(&env, &client);
+
+    let creator = Address::generate(&env);
+    let project_id = String::from_str(&env, "test_project");
+    let result = client.try_create_project(
+        &project_id,
+        &creator,
+        &String::from_str(&env, "https://example.com/metadata"),
+        &BytesN::from_array(&env, &[0; 32]),
+        &1000,
+        &5,
+        &FundingModel::AllOrNothing,
+    );
+    assert_eq!(result, Err(Ok(BoundlessError::TransferFailed)));
+}
+
+#[test]
+fn test_reclaim_bond_after_close() {
+    let env = Env::default();
+    let contract_id = env.register(BoundlessContract, ());
+    let client = BoundlessContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    let (token, _) = setup_bond_requirement(&env, &client);
+
+    let creator = Address::generate(&env);
+    StellarAssetClient::new(&env, &token.address).mint(&creator, &100);
+    let project_id = String::from_str(&env, "test_project");
+    client.create_project(
+        &project_id,
+        &creator,
+        &String::from_str(&env, "https://example.com/metadata"),
+        &BytesN::from_array(&env, &[0; 32]),
+        &1000,
+        &5,
+        &FundingModel::AllOrNothing,
+    );
+    assert_eq!(
+        client.try_reclaim_bond(&project_id),
+        Err(Ok(BoundlessError::InvalidOperation))
+    );
+
+    client.close_project(&project_id, &creator);
+    client.reclaim_bond(&project_id);
+    assert_eq!(token.balance(&creator), 100);
+    assert_eq!(
+        client.get_creator_bond(&project_id).status,
+        BondStatus::Returned
+    );
+    assert_eq!(
+        client.try_reclaim_bond(&project_id),
+        Err(Ok(BoundlessError::AlreadyClaimed))
+    );
+}
+
+#[test]
+fn test_reclaim_bond_after_donation_period() {
+    let env = Env::default();
+    let contract_id = env.register(BoundlessContract, ());
+    let client = BoundlessContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    let (token, _) = setup_bond_requirement(&env, &client);
+
+    let creator = Address::generate(&env);
+    StellarAssetClient::new(&env, &token.address).mint(&creator, &100);
+    let project_id = String::from_str(&env, "test_project");
+    client.create_project(
+        &project_id,
+        &creator,
+        &String::from_str(&env, "https://example.com/metadata"),
+        &BytesN::from_array(&env, &[0; 32]),
+        &1000,
+        &0,
+        &FundingModel::Donation,
+    );
+    env.as_contract(&contract_id, || {
+        let mut project: Project = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id.clone()))
+            .unwrap();
+        project.status = ProjectStatus::Funded;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id.clone()), &project);
+    });
+
+    // Donations may still arrive until the deadline
+    assert_eq!(
+        client.try_reclaim_bond(&project_id),
+        Err(Ok(BoundlessError::InvalidOperation))
+    );
+
+    env.ledger()
+        .set_timestamp(FUNDING_PERIOD_LEDGERS as u64 + 1);
+    client.reclaim_bond(&project_id);
+    assert_eq!(token.balance(&creator), 100);
+    assert_eq!(
+        client.get_creator_bond(&project_id).status,
+        BondStatus::Returned
+    );
+}
+
+#[test]
+fn test_set_payout_splits_before_funding() {
+    let env = Env::default();
+    let contract_id = env.register(BoundlessContract, ());
+    let client = BoundlessContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let creator = Address::generate(&env);
+    let project_id = String::from_str(&env, "test_project");
+    client.create_project(
+        &project_id,
+        &creator,
+        &String::from_str(&env, "https://example.com/metadata"),
+        &BytesN::from_array(&env, &[0; 32]),
+        &1000,
+        &5,
+        &FundingModel::AllOrNothing,
+    );
+    let splits = vec![
+        &env,
+        PayoutSplit {
+            recipient: creator.clone(),
+            bps: 5000,
+        },
+        PayoutSplit {
+            recipient: Address::generate(&env),
+            bps: 5000,
+        },
+    ];
+    client.set_payout_splits(&project_id, &creator, &splits);
+    assert_eq!(client.get_payout_splits(&project_id), splits);
+    assert!(client.try_get_payout_split_proposal(&project_id).is_err());
+}
+
+#[test]
+fn test_set_payout_splits_invalid_total() {
+    let env = Env::default();
+    let contract_id = env.register(BoundlessContract, ());
+    let client = BoundlessContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let creator = Address::generate(&env);
+    let project_id = String::from_str(&env, "test_project");
+    client.create_project(
+        &project_id,
+        &creator,
+        &String::from_str(&env, "https://example.com/metadata"),
+        &BytesN::from_array(&env, &[0; 32]),
+        &1000,
+        &5,
+        &FundingModel::AllOrNothing,
+    );
+    let splits = vec![
+        &env,
+        PayoutSplit {
+            recipient: creator.clone(),
+            bps: 9000,
+        },
+    ];
+    let result = client.try_set_payout_splits(&project_id, &creator, &splits);
+    assert_eq!(result, Err(Ok(BoundlessError::InvalidShare)));
+}
+
+#[test]
+fn test_transfer_project_ownership() {
+    let env = Env::default();
+    let contract_id = env.register(BoundlessContract, ());
+    let client = BoundlessContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let creator = Address::generate(&env);
+    let project_id = String::from_str(&env, "test_project");
+    client.create_project(
+        &project_id,
+        &creator,
+        &String::from_str(&env, "https://example.com/metadata"),
+        &BytesN::from_array(&env, &[0; 32]),
+        &1000,
+        &5,
+        &FundingModel::AllOrNothing,
+    );
+
+    let new_owner = Address::generate(&env);
+    client.transfer_project_ownership(&project_id, &creator, &new_owner);
+    assert_eq!(client.get_project(&project_id).creator, creator);
+    assert_eq!(client.get_pending_owner(&project_id), new_owner);
+
+    let impostor = Address::generate(&env);
+    let result = client.try_accept_project_ownership(&project_id, &impostor);
+    assert_eq!(result, Err(Ok(BoundlessError::Unauthorized)));
+
+    client.accept_project_ownership(&project_id, &new_owner);
+    assert_eq!(client.get_project(&project_id).creator, new_owner);
+    assert!(client.try_get_pending_owner(&project_id).is_err());
+    let result = client.try_close_project(&project_id, &creator);
+    assert_eq!(result, Err(Ok(BoundlessError::Unauthorized)));
+    client.close_project(&project_id, &new_owner);
+}
+
+#[test]
+fn test_post_project_updates() {
+    let env = Env::default();
+    let contract_id = env.register(BoundlessContract, ());
+    let client = BoundlessContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let creator = Address::generate(&env);
+    let project_id = String::from_str(&env, "test_project");
+    client.create_project(
+        &project_id,
+        &creator,
+        &String::from_str(&env, "https://example.com/metadata"),
+        &BytesN::from_array(&env, &[0; 32]),
+        &1000,
+        &5,
+        &FundingModel::AllOrNothing,
+    );
+    client.add_milestone(
+        &project_id,
+        &creator,
+        &String::from_str(&env, "milestone"),
+        &200,
+        &1000,
+    );
+
+    for i in 0..3u8 {
+        let index = client.post_project_update(
+            &project_id,
+            &creator,
+            &String::from_str(&env, "ipfs://update"),
+            &BytesN::from_array(&env, &[i; 32]),
+            &if i == 2 { Some(0) } else { None },
+        );
+        assert_eq!(index, i as u32);
+    }
+    assert_eq!(client.get_project_update_count(&project_id), 3);
+
+    let page = client.get_project_updates(&project_id, &1, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get_unchecked(0).index, 1);
+    assert_eq!(page.get_unchecked(1).milestone_number, Some(0));
+    assert_eq!(
+        page.get_unchecked(1).hash,
+        BytesN::from_array(&env, &[2; 32])
+    );
+    assert_eq!(client.get_project_updates(&project_id, &3, &10).len(), 0);
+}
+
+#[test]
+fn test_post_project_update_invalid() {
+    let env = Env::default();
+    let contract_id = env.register(BoundlessContract, ());
+    let client = BoundlessContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let creator = Address::generate(&env);
+    let project_id = String::from_str(&env, "test_project");
+    client.create_project(
+        &project_id,
+        &creator,
+        &String::from_str(&env, "https://example.com/metadata"),
+        &BytesN::from_array(&env, &[0; 32]),
+        &1000,
+        &5,
+        &FundingModel::AllOrNothing,
+    );
+
+    let uri = String::from_str(&env, "ipfs://update");
+    let hash = BytesN::from_array(&env, &[1; 32]);
+    let result =
+        client.try_post_project_update(&project_id, &Address::generate(&env), &uri, &hash, &None);
+    assert_eq!(result, Err(Ok(BoundlessError::Unauthorized)));
+    let result = client.try_post_project_update(&project_id, &creator, &uri, &hash, &Some(0));
+    assert_eq!(result, Err(Ok(BoundlessError::InvalidMilestone)));
+}
@@ -0,0 +1,123 @@
+#![cfg(test)]
+
+use crate::{
+    datatypes::{BoundlessError, FundingModel, PayoutSplit},
+    BoundlessContract, BoundlessContractClient,
+};
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, String};
+
+fn setup_team_project<'a>(env: &Env) -> (BoundlessContractClient<'a>, String, Address, Address) {
+    let contract_id = env.register(BoundlessContract, ());
+    let client = BoundlessContractClient::new(env, &contract_id);
+    env.mock_all_auths();
+
+    let creator = Address::generate(env);
+    let project_id = String::from_str(env, "test_project");
+    client.create_project(
+        &project_id,
+        &creator,
+        &String::from_str(env, "https://example.com/metadata"),
+        &BytesN::from_array(env, &[0; 32]),
+        &1000,
+        &5,
+        &FundingModel::AllOrNothing,
+    );
+
+    // A single-member team approves its own changes immediately
+    let member = Address::generate(env);
+    client.add_team_member(&project_id, &creator, &member);
+    client.set_team_threshold(&project_id, &creator, &2);
+
+    (client, project_id, creator, member)
+}
+
+#[test]
+fn test_team_action_needs_threshold_approvals() {
+    let env = Env::default();
+    let (client, project_id, creator, member) = setup_team_project(&env);
+    let team = client.get_project_team(&project_id);
+    assert_eq!(team.members.len(), 2);
+    assert_eq!(team.threshold, 2);
+
+    let new_uri = String::from_str(&env, "https://example.com/new");
+    let new_hash = BytesN::from_array(&env, &[3; 32]);
+    client.update_project_metadata(&project_id, &creator, &new_uri, &new_hash);
+    assert_eq!(
+        client.get_project(&project_id).metadata_uri,
+        String::from_str(&env, "https://example.com/metadata")
+    );
+    assert_eq!(client.get_pending_team_actions(&project_id).len(), 1);
+
+    client.update_project_metadata(&project_id, &member, &new_uri, &new_hash);
+    assert_eq!(client.get_project(&project_id).metadata_uri, new_uri);
+    assert_eq!(client.get_pending_team_actions(&project_id).len(), 0);
+}
+
+#[test]
+fn test_team_action_approved_twice() {
+    let env = Env::default();
+    let (client, project_id, creator, _) = setup_team_project(&env);
+
+    client.close_project(&project_id, &creator);
+    let result = client.try_close_project(&project_id, &creator);
+    assert_eq!(result, Err(Ok(BoundlessError::AlreadyVoted)));
+    assert!(!client.get_project(&project_id).is_closed);
+}
+
+#[test]
+fn test_team_action_not_member() {
+    let env = Env::default();
+    let (client, project_id, _, _) = setup_team_project(&env);
+
+    let outsider = Address::generate(&env);
+    let result = client.try_close_project(&project_id, &outsider);
+    assert_eq!(result, Err(Ok(BoundlessError::Unauthorized)));
+}
+
+#[test]
+fn test_remove_team_member() {
+    let env = Env::default();
+    let (client, project_id, creator, member) = setup_team_project(&env);
+
+    // Removing a member would leave fewer members than the threshold
+    let result = client.try_remove_team_member(&project_id, &creator, &member);
+    assert_eq!(result, Err(Ok(BoundlessError::InvalidOperation)));
+
+    client.set_team_threshold(&project_id, &creator, &1);
+    client.set_team_threshold(&project_id, &member, &1);
+    assert_eq!(client.get_project_team(&project_id).threshold, 1);
+    client.remove_team_member(&project_id, &creator, &member);
+    assert_eq!(client.get_project_team(&project_id).members.len(), 1);
+}
+
+#[test]
+fn test_payout_and_plan_changes_need_team_approval() {
+    let env = Env::default();
+    let (client, project_id, creator, member) = setup_team_project(&env);
+
+    let splits = vec![
+        &env,
+        PayoutSplit {
+            recipient: creator.clone(),
+            bps: 10000,
+        },
+    ];
+    client.set_payout_splits(&project_id, &creator, &splits);
+    client.set_upfront_bps(&project_id, &creator, &1000);
+    let description = String::from_str(&env, "milestone");
+    assert_eq!(
+        client.add_milestone(&project_id, &creator, &description, &200, &1000),
+        0
+    );
+    assert_eq!(client.get_pending_team_actions(&project_id).len(), 3);
+    assert_eq!(client.get_project(&project_id).milestones.len(), 0);
+    assert!(client.try_get_upfront_tranche(&project_id).is_err());
+
+    client.set_payout_splits(&project_id, &member, &splits);
+    client.set_upfront_bps(&project_id, &member, &1000);
+    client.add_milestone(&project_id, &member, &description, &200, &1000);
+    assert_eq!(client.get_pending_team_actions(&project_id).len(), 0);
+    assert_eq!(client.get_payout_splits(&project_id), splits);
+    assert_eq!(client.get_upfront_tranche(&project_id).bps, 1000);
+    assert_eq!(client.get_project(&project_id).milestones.len(), 1);
+}
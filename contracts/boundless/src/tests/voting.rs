@@ -2,7 +2,8 @@
 
 use crate::{
     datatypes::{
-        BoundlessError, Project, ProjectStatus, FUNDING_PERIOD_LEDGERS, VOTING_PERIOD_LEDGERS,
+        BoundlessError, FundingModel, Project, ProjectStatus, FUNDING_PERIOD_LEDGERS,
+        VOTING_PERIOD_LEDGERS,
     },
     BoundlessContract, BoundlessContractClient,
 };
@@ -27,8 +28,10 @@ fn test_vote_project() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let voter = Address::generate(&env);
     let vote_value = 1;
@@ -56,8 +59,10 @@ fn test_vote_project_unauthorized() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let vote_value = 1;
     client.vote_project(&project_id, &creator, &vote_value);
@@ -80,8 +85,10 @@ fn test_vote_project_invalid_vote_value() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let vote_value = 2;
     client.vote_project(&project_id, &creator, &vote_value);
@@ -104,8 +111,10 @@ fn test_vote_project_project_closed() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let vote_value = 1;
     client.vote_project(&project_id, &creator, &vote_value);
@@ -130,8 +139,10 @@ fn test_vote_project_funding_period_ended() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let vote_value = 1;
     client.vote_project(&project_id, &creator, &vote_value);
@@ -155,8 +166,10 @@ fn test_withdraw_vote() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let voter = Address::generate(&env);
     let vote_value = 1;
@@ -183,8 +196,10 @@ fn test_withdraw_vote_unauthorized() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let voter = Address::generate(&env);
     let vote_value = 1;
@@ -209,8 +224,10 @@ fn test_withdraw_vote_project_closed() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let vote_value = 1;
     client.vote_project(&project_id, &creator, &vote_value);
@@ -235,8 +252,10 @@ fn test_withdraw_vote_voting_period_ended() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let vote_value = 1;
     client.vote_project(&project_id, &creator, &vote_value);
@@ -261,8 +280,10 @@ fn test_withdraw_vote_already_voted() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let vote_value = 1;
     client.vote_project(&project_id, &creator, &vote_value);
@@ -286,8 +307,10 @@ fn test_has_voted() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let voter = Address::generate(&env);
     let vote_value = 1;
@@ -311,8 +334,10 @@ fn test_get_vote() {
         &project_id,
         &creator,
         &metadata_uri,
+        &BytesN::from_array(&env, &[0; 32]),
         &funding_target,
         &milestone_count,
+        &FundingModel::AllOrNothing,
     );
     let voter = Address::generate(&env);
     let vote_value = 1;